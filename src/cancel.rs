@@ -0,0 +1,59 @@
+//! Cooperative cancellation for long-running solvers. Solvers can't be killed from the outside,
+//! so the slow ones poll the token of the thread they were started on and, once it is
+//! cancelled, return early with their best answer so far.
+
+use std::{
+    cell::RefCell,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+#[derive(Debug, Clone, Default)]
+pub struct Token(Arc<AtomicBool>);
+
+impl Token {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Cheap enough to check in hot loops.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+thread_local! {
+    static CURRENT: RefCell<Token> = RefCell::default();
+}
+
+/// The token of the running solver. Never cancelled unless the solver was started with
+/// [`install`]. Solvers should get it once up front, as worker threads don't share it.
+pub fn current() -> Token {
+    CURRENT.with(|current| current.borrow().clone())
+}
+
+/// Makes `token` the current token of this thread.
+pub fn install(token: Token) {
+    CURRENT.with(|current| *current.borrow_mut() = token);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokens_are_per_thread() {
+        let token = Token::default();
+        token.cancel();
+
+        let installed = std::thread::spawn(|| {
+            install(token);
+            current().is_cancelled()
+        });
+
+        assert!(installed.join().unwrap());
+        assert!(!current().is_cancelled());
+    }
+}
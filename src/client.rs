@@ -0,0 +1,492 @@
+use std::{
+    fmt::{Display, Formatter},
+    fs,
+    io::Read,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::html;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+const SESSION_FILE: &str = ".aoc-session";
+const USER_AGENT: &str = concat!(
+    "github.com/paavohuhtala/advent-of-code-2022 ",
+    env!("CARGO_PKG_NAME"),
+    "/",
+    env!("CARGO_PKG_VERSION")
+);
+
+#[derive(Debug)]
+pub enum ClientError {
+    MissingSession,
+    SessionExpired,
+    NotAvailable { year: u16, day: u8 },
+    Status(u16, String),
+    Transport(String),
+    Io(PathBuf, std::io::Error),
+}
+
+impl Display for ClientError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientError::MissingSession => write!(
+                f,
+                "no session token found; set AOC_SESSION or write it to {}",
+                SESSION_FILE
+            ),
+            ClientError::SessionExpired => write!(
+                f,
+                "session token was rejected; it has probably expired, log in again and replace it"
+            ),
+            ClientError::NotAvailable { year, day } => {
+                write!(f, "puzzle {} day {} is not available (yet)", year, day)
+            }
+            ClientError::Status(status, body) => {
+                write!(f, "unexpected response {}: {}", status, body.trim())
+            }
+            ClientError::Transport(message) => write!(f, "request failed: {}", message),
+            ClientError::Io(path, error) => write!(f, "{}: {}", path.display(), error),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubmitOutcome {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    RateLimited,
+    AlreadySolved,
+}
+
+impl SubmitOutcome {
+    pub fn name(self) -> &'static str {
+        match self {
+            SubmitOutcome::Correct => "correct",
+            SubmitOutcome::Wrong => "wrong",
+            SubmitOutcome::TooHigh => "too-high",
+            SubmitOutcome::TooLow => "too-low",
+            SubmitOutcome::RateLimited => "rate-limited",
+            SubmitOutcome::AlreadySolved => "already-solved",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "correct" => Some(SubmitOutcome::Correct),
+            "wrong" => Some(SubmitOutcome::Wrong),
+            "too-high" => Some(SubmitOutcome::TooHigh),
+            "too-low" => Some(SubmitOutcome::TooLow),
+            "rate-limited" => Some(SubmitOutcome::RateLimited),
+            "already-solved" => Some(SubmitOutcome::AlreadySolved),
+            _ => None,
+        }
+    }
+
+    pub fn is_wrong(self) -> bool {
+        matches!(
+            self,
+            SubmitOutcome::Wrong | SubmitOutcome::TooHigh | SubmitOutcome::TooLow
+        )
+    }
+}
+
+/// Classifies the page the site returns after posting an answer. Returns `None` if the page
+/// doesn't look like any of the known responses.
+pub fn parse_submit_response(html: &str) -> Option<SubmitOutcome> {
+    let text = main_text(html);
+
+    if text.contains("That's the right answer") {
+        Some(SubmitOutcome::Correct)
+    } else if text.contains("That's not the right answer") {
+        if text.contains("answer is too high") {
+            Some(SubmitOutcome::TooHigh)
+        } else if text.contains("answer is too low") {
+            Some(SubmitOutcome::TooLow)
+        } else {
+            Some(SubmitOutcome::Wrong)
+        }
+    } else if text.contains("You gave an answer too recently") {
+        Some(SubmitOutcome::RateLimited)
+    } else if text.contains("You don't seem to be solving the right level") {
+        Some(SubmitOutcome::AlreadySolved)
+    } else {
+        None
+    }
+}
+
+/// The text content of the page's `<article>`, which is where the site puts its verdict.
+pub fn main_text(html: &str) -> String {
+    let article = html::elements(html, "article")
+        .first()
+        .copied()
+        .unwrap_or(html);
+
+    html::text_content(article)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Reads the session token, preferring an explicitly given one (usually from `AOC_SESSION`)
+/// over the contents of `.aoc-session` in the working directory.
+pub fn resolve_session(session: Option<String>) -> Result<String, ClientError> {
+    let session = match session {
+        Some(session) => session,
+        None => match fs::read_to_string(SESSION_FILE) {
+            Ok(session) => session,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+                return Err(ClientError::MissingSession)
+            }
+            Err(error) => return Err(ClientError::Io(SESSION_FILE.into(), error)),
+        },
+    };
+
+    let session = session.trim();
+
+    if session.is_empty() {
+        return Err(ClientError::MissingSession);
+    }
+
+    Ok(session.to_string())
+}
+
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: String) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(30))
+            .redirects(0)
+            .build();
+
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session,
+            agent,
+        }
+    }
+
+    pub fn fetch_input(&self, year: u16, day: u8) -> Result<String, ClientError> {
+        let url = format!("{}/{}/day/{}/input", self.base_url, year, day);
+
+        let response = self
+            .agent
+            .get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call();
+
+        match response {
+            Ok(response) if response.status() == 200 => read_body(response),
+            Ok(response) | Err(ureq::Error::Status(_, response)) => {
+                Err(classify_error(year, day, response))
+            }
+            Err(ureq::Error::Transport(transport)) => {
+                Err(ClientError::Transport(transport.to_string()))
+            }
+        }
+    }
+
+    /// Posts an answer and returns the classified outcome along with the site's message.
+    pub fn submit_answer(
+        &self,
+        year: u16,
+        day: u8,
+        level: u8,
+        answer: &str,
+    ) -> Result<(SubmitOutcome, String), ClientError> {
+        let url = format!("{}/{}/day/{}/answer", self.base_url, year, day);
+
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &level.to_string()), ("answer", answer)]);
+
+        let body = match response {
+            Ok(response) if response.status() == 200 => read_body(response)?,
+            Ok(response) | Err(ureq::Error::Status(_, response)) => {
+                return Err(classify_error(year, day, response))
+            }
+            Err(ureq::Error::Transport(transport)) => {
+                return Err(ClientError::Transport(transport.to_string()))
+            }
+        };
+
+        match parse_submit_response(&body) {
+            Some(outcome) => Ok((outcome, main_text(&body))),
+            None if body.contains("log in") => Err(ClientError::SessionExpired),
+            None => Err(ClientError::Status(200, main_text(&body))),
+        }
+    }
+}
+
+fn read_body(response: ureq::Response) -> Result<String, ClientError> {
+    let mut body = String::new();
+    response
+        .into_reader()
+        .read_to_string(&mut body)
+        .map_err(|error| ClientError::Transport(error.to_string()))?;
+    Ok(body)
+}
+
+fn classify_error(year: u16, day: u8, response: ureq::Response) -> ClientError {
+    let status = response.status();
+    let login_redirect = status == 302
+        && response
+            .header("Location")
+            .is_some_and(|location| location.contains("login"));
+
+    let body = match read_body(response) {
+        Ok(body) => body,
+        Err(error) => return error,
+    };
+
+    // The site answers requests without a valid session with "Please log in", either as a
+    // 400 or as a redirect to the login page depending on how the cookie is broken. Other
+    // client errors, e.g. a rate limit, are reported as they are.
+    if body.contains("log in") || login_redirect {
+        ClientError::SessionExpired
+    } else if status == 404 {
+        ClientError::NotAvailable { year, day }
+    } else {
+        ClientError::Status(status, body)
+    }
+}
+
+pub struct InputCache {
+    dir: PathBuf,
+}
+
+impl Default for InputCache {
+    fn default() -> Self {
+        Self::new(Path::new(env!("CARGO_MANIFEST_DIR")).join("src"))
+    }
+}
+
+impl InputCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    pub fn path(&self, year: u16, day: u8) -> PathBuf {
+        self.dir
+            .join(format!("y{}", year))
+            .join(format!("day{}.input", day))
+    }
+
+    /// Returns the cached input for the day, downloading it first if it isn't on disk yet.
+    /// The returned flag tells whether a request was made.
+    pub fn get_or_fetch(
+        &self,
+        client: &Client,
+        year: u16,
+        day: u8,
+    ) -> Result<(PathBuf, bool), ClientError> {
+        let path = self.path(year, day);
+
+        if path.exists() {
+            return Ok((path, false));
+        }
+
+        let input = client.fetch_input(year, day)?;
+        let dir = path.parent().unwrap();
+
+        fs::create_dir_all(dir).map_err(|error| ClientError::Io(dir.to_path_buf(), error))?;
+        fs::write(&path, input).map_err(|error| ClientError::Io(path.clone(), error))?;
+
+        Ok((path, true))
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        sync::mpsc,
+        thread,
+    };
+
+    use super::*;
+
+    /// Serves the given canned responses to consecutive connections and sends each raw request
+    /// (request line, headers and body) back through the returned channel.
+    pub(crate) fn serve(responses: Vec<String>) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut head = String::new();
+                let mut content_length = 0;
+
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+
+                    if let Some(length) = line.to_ascii_lowercase().strip_prefix("content-length:")
+                    {
+                        content_length = length.trim().parse().unwrap();
+                    }
+
+                    if line == "\r\n" || line.is_empty() {
+                        break;
+                    }
+
+                    head.push_str(&line);
+                }
+
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+                head.push_str(&String::from_utf8(body).unwrap());
+
+                tx.send(head).unwrap();
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+
+        (base_url, rx)
+    }
+
+    pub(crate) fn response(status: &str, body: &str) -> String {
+        format!(
+            "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        )
+    }
+
+    pub(crate) fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("advent-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn fetch_sends_session_and_user_agent() {
+        let (base_url, requests) = serve(vec![response("200 OK", "1000\n2000\n")]);
+        let client = Client::new(&base_url, "abc123".to_string());
+
+        let input = client.fetch_input(2022, 1).unwrap();
+        let request = requests.recv().unwrap();
+
+        assert_eq!(input, "1000\n2000\n");
+        assert!(request.starts_with("GET /2022/day/1/input "));
+        assert!(request.contains("session=abc123"));
+        assert!(request.contains(USER_AGENT));
+    }
+
+    #[test]
+    fn fetch_reports_expired_session() {
+        let body = "Puzzle inputs differ by user.  Please log in to get your puzzle input.";
+        let (base_url, _requests) = serve(vec![response("400 Bad Request", body)]);
+        let client = Client::new(&base_url, "stale".to_string());
+
+        let error = client.fetch_input(2022, 1).unwrap_err();
+        assert!(matches!(error, ClientError::SessionExpired));
+    }
+
+    #[test]
+    fn classifies_error_responses() {
+        let redirect = "HTTP/1.1 302 Found\r\nLocation: /auth/login\r\n\
+                        Content-Length: 0\r\nConnection: close\r\n\r\n"
+            .to_string();
+        let (base_url, _requests) = serve(vec![
+            redirect,
+            response("403 Forbidden", "Slow down"),
+            response("404 Not Found", ""),
+        ]);
+        let client = Client::new(&base_url, "abc123".to_string());
+
+        let error = client.fetch_input(2022, 1).unwrap_err();
+        assert!(matches!(error, ClientError::SessionExpired));
+
+        let error = client.fetch_input(2022, 1).unwrap_err();
+        assert!(matches!(error, ClientError::Status(403, _)));
+
+        let error = client.fetch_input(2022, 1).unwrap_err();
+        assert!(matches!(error, ClientError::NotAvailable { .. }));
+    }
+
+    #[test]
+    fn submit_posts_level_and_answer() {
+        let page = "<html><main><article><p>That's not the right answer; your answer is too \
+                    low. <a href=\"/2022/day/1\">[Return to Day 1]</a></p></article></main>";
+        let (base_url, requests) = serve(vec![response("200 OK", page)]);
+        let client = Client::new(&base_url, "abc123".to_string());
+
+        let (outcome, message) = client.submit_answer(2022, 1, 2, "1234").unwrap();
+        let request = requests.recv().unwrap();
+
+        assert_eq!(outcome, SubmitOutcome::TooLow);
+        assert!(message.starts_with("That's not the right answer; your answer is too low."));
+        assert!(request.starts_with("POST /2022/day/1/answer "));
+        assert!(request.ends_with("level=2&answer=1234"));
+    }
+
+    #[test]
+    fn submit_responses_are_classified() {
+        let cases = [
+            (
+                "That's the right answer! You are one gold star closer.",
+                Some(SubmitOutcome::Correct),
+            ),
+            (
+                "That's not the right answer. If you're stuck, ...",
+                Some(SubmitOutcome::Wrong),
+            ),
+            (
+                "That's not the right answer; your answer is too high.",
+                Some(SubmitOutcome::TooHigh),
+            ),
+            (
+                "You gave an answer too recently; You have 38s left to wait.",
+                Some(SubmitOutcome::RateLimited),
+            ),
+            (
+                "You don't seem to be solving the right level.  Did you already complete it?",
+                Some(SubmitOutcome::AlreadySolved),
+            ),
+            ("Something else entirely", None),
+        ];
+
+        for (text, expected) in cases {
+            let html = format!("<article><p>{}</p></article>", text);
+            assert_eq!(parse_submit_response(&html), expected, "{}", text);
+        }
+    }
+
+    #[test]
+    fn cached_input_is_fetched_once() {
+        let (base_url, requests) =
+            serve(vec![response("200 OK", "mjqjpqmgbljsphdztnvjfqwrcgsmlb")]);
+        let client = Client::new(&base_url, "abc123".to_string());
+        let cache = InputCache::new(temp_dir("input-cache"));
+
+        let (path, fetched) = cache.get_or_fetch(&client, 2022, 6).unwrap();
+        assert!(fetched);
+        let (_, fetched) = cache.get_or_fetch(&client, 2022, 6).unwrap();
+        assert!(!fetched);
+
+        assert_eq!(requests.try_iter().count(), 1);
+        assert_eq!(
+            fs::read_to_string(path).unwrap(),
+            "mjqjpqmgbljsphdztnvjfqwrcgsmlb"
+        );
+    }
+}
@@ -0,0 +1,198 @@
+use std::io::{self, BufRead, IsTerminal, Write};
+
+/// A puzzle simulation that can be advanced one step at a time.
+pub trait Simulation: Clone {
+    /// Advances the simulation by one step. Returns false if it had already finished.
+    fn step(&mut self) -> bool;
+
+    fn render(&self) -> String;
+}
+
+/// How many checkpoints to keep before thinning them out. Stepping backwards replays the
+/// simulation from the closest earlier checkpoint, so this bounds memory use while keeping the
+/// replays short.
+const MAX_CHECKPOINTS: usize = 256;
+
+pub struct Debugger<S> {
+    current: S,
+    step: usize,
+    finished: bool,
+    /// `checkpoints[i]` is the state after `i * interval` steps.
+    checkpoints: Vec<S>,
+    interval: usize,
+}
+
+impl<S: Simulation> Debugger<S> {
+    pub fn new(initial: S) -> Self {
+        Self {
+            checkpoints: vec![initial.clone()],
+            current: initial,
+            step: 0,
+            finished: false,
+            interval: 1,
+        }
+    }
+
+    pub fn current(&self) -> &S {
+        &self.current
+    }
+
+    pub fn step(&self) -> usize {
+        self.step
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Takes up to `steps` steps forward, stopping early if the simulation finishes.
+    pub fn forward(&mut self, steps: usize) {
+        for _ in 0..steps {
+            if !self.current.step() {
+                self.finished = true;
+                break;
+            }
+
+            self.step += 1;
+
+            if self.step == self.checkpoints.len() * self.interval {
+                self.checkpoints.push(self.current.clone());
+
+                if self.checkpoints.len() > MAX_CHECKPOINTS {
+                    self.thin_checkpoints();
+                }
+            }
+        }
+    }
+
+    pub fn back(&mut self, steps: usize) {
+        self.goto(self.step.saturating_sub(steps));
+    }
+
+    pub fn goto(&mut self, target: usize) {
+        if target < self.step {
+            let index = (target / self.interval).min(self.checkpoints.len() - 1);
+            self.current = self.checkpoints[index].clone();
+            self.step = index * self.interval;
+            self.finished = false;
+        }
+
+        self.forward(target - self.step);
+    }
+
+    pub fn run_to_end(&mut self) {
+        while !self.finished {
+            self.forward(self.interval.max(1000));
+        }
+    }
+
+    fn thin_checkpoints(&mut self) {
+        let mut index = 0;
+        self.checkpoints.retain(|_| {
+            index += 1;
+            index % 2 == 1
+        });
+        self.interval *= 2;
+    }
+}
+
+const HELP: &str = "Enter/n [k]: forward, p [k]: back, g <step>: go to step, e: end, q: quit";
+
+/// Shows the simulation and steps through it as the user commands.
+pub fn interact<S: Simulation>(mut debugger: Debugger<S>) -> io::Result<()> {
+    let stdin = io::stdin();
+    let is_terminal = io::stdout().is_terminal();
+    let mut message = HELP.to_string();
+
+    loop {
+        if is_terminal {
+            // Clear the screen so that the state stays in place while stepping
+            print!("\x1b[2J\x1b[H");
+        }
+
+        println!("{}", debugger.current().render());
+        println!();
+        println!(
+            "Step {}{}",
+            debugger.step(),
+            if debugger.is_finished() {
+                " (finished)"
+            } else {
+                ""
+            }
+        );
+        println!("{}", message);
+        print!("> ");
+        io::stdout().flush()?;
+
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line)? == 0 {
+            return Ok(());
+        }
+
+        let mut words = line.split_whitespace();
+        let command = words.next().unwrap_or("n");
+        let count = words.next().map(|count| count.parse::<usize>());
+
+        message = HELP.to_string();
+
+        match (command, count) {
+            (_, Some(Err(error))) => message = format!("Invalid number: {}", error),
+            ("n", count) => debugger.forward(count.map_or(1, Result::unwrap)),
+            ("p", count) => debugger.back(count.map_or(1, Result::unwrap)),
+            ("g", Some(Ok(step))) => debugger.goto(step),
+            ("e", None) => debugger.run_to_end(),
+            ("q", None) => return Ok(()),
+            _ => message = format!("Unknown command. {}", HELP),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone)]
+    struct Counter {
+        value: u64,
+        limit: u64,
+    }
+
+    impl Simulation for Counter {
+        fn step(&mut self) -> bool {
+            if self.value == self.limit {
+                return false;
+            }
+
+            self.value += 1;
+            true
+        }
+
+        fn render(&self) -> String {
+            self.value.to_string()
+        }
+    }
+
+    #[test]
+    fn steps_back_across_thinned_checkpoints() {
+        let mut debugger = Debugger::new(Counter {
+            value: 0,
+            limit: 10_000,
+        });
+
+        debugger.run_to_end();
+        assert_eq!(debugger.step(), 10_000);
+        assert!(debugger.checkpoints.len() <= MAX_CHECKPOINTS);
+
+        debugger.back(1);
+        assert_eq!(debugger.current().value, 9_999);
+
+        debugger.goto(1234);
+        assert_eq!(debugger.current().value, 1234);
+        assert!(!debugger.is_finished());
+
+        debugger.forward(20_000);
+        assert_eq!(debugger.current().value, 10_000);
+        assert!(debugger.is_finished());
+    }
+}
@@ -0,0 +1,206 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{html, solution::Part};
+
+/// An example input from a puzzle description together with the answer the description gives
+/// for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub part: Part,
+    pub input: String,
+    pub answer: String,
+}
+
+/// Pulls the examples out of a saved puzzle page. Each part's description is its own
+/// `<article>`; the example is its first `<pre><code>` block and the expected answer is the
+/// last emphasized `<code><em>` in it, which is how the descriptions are written. A part
+/// without its own example block reuses the previous part's.
+///
+/// `block` overrides the example with the n:th (1-based) block of the whole page, for days
+/// where the first block isn't the one the answer refers to.
+pub fn extract(page: &str, block: Option<usize>) -> Vec<Example> {
+    let articles = html::elements(page, "article");
+
+    let all_blocks = articles
+        .iter()
+        .flat_map(|article| code_blocks(article))
+        .collect::<Vec<_>>();
+
+    let mut examples = Vec::new();
+    let mut previous_input = None;
+
+    for (article, part) in articles.iter().zip([Part::A, Part::B]) {
+        let input = match block {
+            Some(n) => all_blocks.get(n.saturating_sub(1)).cloned(),
+            None => code_blocks(article).into_iter().next(),
+        };
+
+        let Some(input) = input.or(previous_input.take()) else {
+            continue;
+        };
+
+        previous_input = Some(input.clone());
+
+        let answer = html::elements(article, "code")
+            .into_iter()
+            .filter(|code| code.trim_start().starts_with("<em>"))
+            .map(html::text_content)
+            .rfind(|answer| !answer.contains('\n'));
+
+        if let Some(answer) = answer {
+            examples.push(Example {
+                part,
+                input,
+                answer: answer.trim().to_string(),
+            });
+        }
+    }
+
+    examples
+}
+
+fn code_blocks(article: &str) -> Vec<String> {
+    html::elements(article, "pre")
+        .into_iter()
+        .map(html::text_content)
+        .collect()
+}
+
+pub fn default_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures")
+}
+
+fn day_dir(dir: &Path, year: u16, day: u8) -> PathBuf {
+    dir.join(format!("y{}", year)).join(format!("day{}", day))
+}
+
+/// Writes the examples as `y{year}/day{n}/{part}.input` and `y{year}/day{n}/{part}.answer`.
+pub fn save(dir: &Path, year: u16, day: u8, examples: &[Example]) -> io::Result<Vec<PathBuf>> {
+    let day_dir = day_dir(dir, year, day);
+    fs::create_dir_all(&day_dir)?;
+
+    let mut written = Vec::new();
+
+    for example in examples {
+        let input_path = day_dir.join(format!("{}.input", example.part));
+        let answer_path = day_dir.join(format!("{}.answer", example.part));
+
+        fs::write(&input_path, &example.input)?;
+        fs::write(&answer_path, format!("{}\n", example.answer))?;
+
+        written.push(input_path);
+        written.push(answer_path);
+    }
+
+    Ok(written)
+}
+
+#[cfg(test)]
+pub fn load(dir: &Path, year: u16, day: u8) -> io::Result<Vec<Example>> {
+    let day_dir = day_dir(dir, year, day);
+    let mut examples = Vec::new();
+
+    for part in [Part::A, Part::B] {
+        let input_path = day_dir.join(format!("{}.input", part));
+
+        if !input_path.exists() {
+            continue;
+        }
+
+        let input = fs::read_to_string(input_path)?;
+        let answer = fs::read_to_string(day_dir.join(format!("{}.answer", part)))?;
+
+        examples.push(Example {
+            part,
+            input,
+            answer: answer.trim_end().to_string(),
+        });
+    }
+
+    Ok(examples)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{client::tests::temp_dir, solution::YEARS};
+
+    const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 1: Calorie Counting ---</h2>
+<p>For example, suppose the Elves finish writing their items' <em>Calories</em>:</p>
+<pre><code>1000
+2000
+
+4000
+</code></pre>
+<p>The fourth Elf is carrying the most with <code>4000</code>, so the answer is <code><em>4000</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>70369</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>In the example above, the top three sum to <code><em>7000</em></code>.</p>
+</article>
+</main>"#;
+
+    #[test]
+    fn extracts_examples_for_both_parts() {
+        let examples = extract(PAGE, None);
+
+        assert_eq!(
+            examples,
+            vec![
+                Example {
+                    part: Part::A,
+                    input: "1000\n2000\n\n4000\n".to_string(),
+                    answer: "4000".to_string(),
+                },
+                Example {
+                    part: Part::B,
+                    input: "1000\n2000\n\n4000\n".to_string(),
+                    answer: "7000".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn saved_examples_load_back() {
+        let dir = temp_dir("fixtures");
+        let examples = extract(PAGE, None);
+
+        save(&dir, 2022, 1, &examples).unwrap();
+        assert_eq!(load(&dir, 2022, 1).unwrap(), examples);
+    }
+
+    #[test]
+    fn decodes_entities_in_blocks() {
+        let page = "<article><pre><code>$ cd /\n&lt;dir&gt; a &amp; b</code></pre>\
+                    <p><code><em>42</em></code></p></article>";
+
+        let examples = extract(page, None);
+        assert_eq!(examples[0].input, "$ cd /\n<dir> a & b");
+    }
+
+    #[test]
+    fn saved_fixtures_match_their_answers() {
+        for solution in YEARS.iter().flat_map(|year| year.solutions) {
+            let examples = load(&default_dir(), solution.year, solution.day).unwrap();
+            let examples = examples
+                .iter()
+                .filter(|example| example.part == solution.part);
+
+            for example in examples {
+                assert_eq!(
+                    (solution.solve)(&example.input).unwrap(),
+                    example.answer,
+                    "day {}{} of {}",
+                    solution.day,
+                    solution.part,
+                    solution.year
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,170 @@
+//! A small built-in fuzzer for the days' parsers. Inputs are either random bytes or mutations of
+//! a valid seed input, which gets much deeper into the parsers than random bytes alone.
+
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::parse::ParseResult;
+
+pub struct Target {
+    pub name: &'static str,
+    pub parse: fn(&str) -> ParseResult<()>,
+    /// A valid input to mutate
+    pub seed: &'static str,
+}
+
+pub const fn target(
+    name: &'static str,
+    parse: fn(&str) -> ParseResult<()>,
+    seed: &'static str,
+) -> Target {
+    Target { name, parse, seed }
+}
+
+/// xorshift64*, plenty for generating test inputs and reproducible from the seed.
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        // The state must never be zero
+        Self(seed ^ 0x9e37_79b9_7f4a_7c15 | 1)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n.max(1) as u64) as usize
+    }
+
+    fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+}
+
+/// Fragments that tend to hit edge cases: separators, signs, huge and empty numbers.
+const DICTIONARY: &[&[u8]] = &[
+    b"\n",
+    b"\n\n",
+    b" ",
+    b",",
+    b"-",
+    b"[",
+    b"]",
+    b"$ ",
+    b"0",
+    b"-1",
+    b"255",
+    b"256",
+    b"99999999999999999999",
+    b"\xff",
+];
+
+fn generate(rng: &mut Rng, seed: &[u8]) -> Vec<u8> {
+    if rng.below(8) == 0 {
+        return (0..rng.below(64)).map(|_| rng.next() as u8).collect();
+    }
+
+    let mut input = seed.to_vec();
+
+    for _ in 0..1 + rng.below(8) {
+        let len = input.len();
+        let at = rng.below(len + 1);
+
+        match rng.below(7) {
+            0 if len > 0 => input[at.min(len - 1)] = rng.next() as u8,
+            1 if len > 0 => input[at.min(len - 1)] = *rng.pick(seed),
+            2 => {
+                let end = (at + rng.below(16)).min(len);
+                input.drain(at..end);
+            }
+            3 => {
+                let end = (at + rng.below(32)).min(len);
+                let copy = input[at..end].to_vec();
+                let to = rng.below(len + 1);
+                input.splice(to..to, copy);
+            }
+            4 => input.truncate(at),
+            _ => {
+                let fragment = rng.pick(DICTIONARY);
+                input.splice(at..at, fragment.iter().copied());
+            }
+        }
+    }
+
+    input
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub accepted: usize,
+    pub rejected: usize,
+}
+
+#[derive(Debug)]
+pub struct Crash {
+    pub input: Vec<u8>,
+    pub message: String,
+}
+
+/// Runs the target's parser on `iterations` generated inputs. Stops at the first panic and
+/// returns the input that caused it.
+pub fn fuzz(target: &Target, iterations: usize, seed: u64) -> Result<Stats, Crash> {
+    let mut rng = Rng::new(seed);
+    let mut stats = Stats::default();
+
+    for _ in 0..iterations {
+        let input = generate(&mut rng, target.seed.as_bytes());
+        let text = String::from_utf8_lossy(&input);
+
+        match panic::catch_unwind(AssertUnwindSafe(|| (target.parse)(&text))) {
+            Ok(Ok(())) => stats.accepted += 1,
+            Ok(Err(_)) => stats.rejected += 1,
+            Err(payload) => {
+                let message = payload
+                    .downcast_ref::<&str>()
+                    .map(|message| message.to_string())
+                    .or_else(|| payload.downcast_ref::<String>().cloned())
+                    .unwrap_or_else(|| "panicked".to_string());
+
+                return Err(Crash { input, message });
+            }
+        }
+    }
+
+    Ok(stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generates_reproducible_inputs() {
+        let inputs = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..10)
+                .map(|_| generate(&mut rng, b"1 2\n3 4\n"))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(inputs(7), inputs(7));
+        assert_ne!(inputs(7), inputs(8));
+    }
+
+    #[test]
+    fn reports_panicking_input() {
+        fn parse(input: &str) -> ParseResult<()> {
+            assert!(!input.contains('\n'), "newline");
+            Ok(())
+        }
+
+        let crash = fuzz(&target("panics", parse, "abc"), 1_000, 1).unwrap_err();
+
+        assert_eq!(crash.message, "newline");
+        assert!(crash.input.contains(&b'\n'));
+    }
+}
@@ -0,0 +1,263 @@
+use std::{
+    fmt::{Display, Formatter},
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{client::SubmitOutcome, solution::Part};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Guess {
+    pub timestamp: u64,
+    pub year: u16,
+    pub day: u8,
+    pub part: Part,
+    pub outcome: SubmitOutcome,
+    pub answer: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Refusal {
+    AlreadySolved(String),
+    KnownWrong,
+    NotBelow(i128),
+    NotAbove(i128),
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadySolved(answer) => {
+                write!(f, "already solved, the answer was {}", answer)
+            }
+            Refusal::KnownWrong => write!(f, "this answer was already rejected"),
+            Refusal::NotBelow(bound) => write!(f, "the answer must be below {}", bound),
+            Refusal::NotAbove(bound) => write!(f, "the answer must be above {}", bound),
+        }
+    }
+}
+
+impl std::error::Error for Refusal {}
+
+/// Every answer ever submitted, stored as one tab-separated line per guess:
+/// `timestamp year day part outcome answer`.
+pub struct GuessHistory {
+    path: PathBuf,
+    guesses: Vec<Guess>,
+}
+
+impl GuessHistory {
+    pub fn default_path() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("guesses.tsv")
+    }
+
+    pub fn load(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(error) => return Err(error),
+        };
+
+        let guesses = contents
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.is_empty())
+            .map(|(i, line)| {
+                parse_guess(line).ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("{}:{}: malformed guess", path.display(), i + 1),
+                    )
+                })
+            })
+            .collect::<io::Result<_>>()?;
+
+        Ok(Self { path, guesses })
+    }
+
+    pub fn guesses(&self, year: u16, day: u8, part: Part) -> impl Iterator<Item = &Guess> {
+        self.guesses
+            .iter()
+            .filter(move |guess| guess.year == year && guess.day == day && guess.part == part)
+    }
+
+    pub fn correct_answer(&self, year: u16, day: u8, part: Part) -> Option<&str> {
+        self.guesses(year, day, part)
+            .find(|guess| guess.outcome == SubmitOutcome::Correct)
+            .map(|guess| guess.answer.as_str())
+    }
+
+    /// Checks the answer against what earlier guesses already tell us, so that we don't waste a
+    /// submission (and a rate limit timeout) on it.
+    pub fn check(&self, year: u16, day: u8, part: Part, answer: &str) -> Result<(), Refusal> {
+        if let Some(correct) = self.correct_answer(year, day, part) {
+            return Err(Refusal::AlreadySolved(correct.to_string()));
+        }
+
+        if self
+            .guesses(year, day, part)
+            .any(|guess| guess.outcome.is_wrong() && guess.answer == answer)
+        {
+            return Err(Refusal::KnownWrong);
+        }
+
+        let Ok(value) = answer.parse::<i128>() else {
+            return Ok(());
+        };
+
+        let numeric_guesses = self
+            .guesses(year, day, part)
+            .filter_map(|guess| Some((guess.outcome, guess.answer.parse::<i128>().ok()?)));
+
+        for (outcome, bound) in numeric_guesses {
+            match outcome {
+                SubmitOutcome::TooHigh if value >= bound => return Err(Refusal::NotBelow(bound)),
+                SubmitOutcome::TooLow if value <= bound => return Err(Refusal::NotAbove(bound)),
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn record(
+        &mut self,
+        year: u16,
+        day: u8,
+        part: Part,
+        outcome: SubmitOutcome,
+        answer: &str,
+    ) -> io::Result<()> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_secs());
+
+        let guess = Guess {
+            timestamp,
+            year,
+            day,
+            part,
+            outcome,
+            answer: answer.to_string(),
+        };
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+
+        writeln!(
+            file,
+            "{}\t{}\t{}\t{}\t{}\t{}",
+            guess.timestamp,
+            guess.year,
+            guess.day,
+            guess.part,
+            guess.outcome.name(),
+            guess.answer
+        )?;
+
+        self.guesses.push(guess);
+
+        Ok(())
+    }
+}
+
+fn parse_guess(line: &str) -> Option<Guess> {
+    let mut fields = line.splitn(6, '\t');
+
+    let timestamp = fields.next()?.parse().ok()?;
+    let year = fields.next()?.parse().ok()?;
+    let day = fields.next()?.parse().ok()?;
+    let part = match fields.next()? {
+        "a" => Part::A,
+        "b" => Part::B,
+        _ => return None,
+    };
+    let outcome = SubmitOutcome::from_name(fields.next()?)?;
+    let answer = fields.next()?.to_string();
+
+    Some(Guess {
+        timestamp,
+        year,
+        day,
+        part,
+        outcome,
+        answer,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::tests::temp_dir;
+
+    #[test]
+    fn refuses_known_wrong_and_out_of_bounds_answers() {
+        let dir = temp_dir("guesses");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("guesses.tsv");
+
+        let mut history = GuessHistory::load(&path).unwrap();
+        history
+            .record(2022, 7, Part::A, SubmitOutcome::TooHigh, "5000")
+            .unwrap();
+        history
+            .record(2022, 7, Part::A, SubmitOutcome::TooLow, "1000")
+            .unwrap();
+        history
+            .record(2022, 7, Part::A, SubmitOutcome::Wrong, "abc")
+            .unwrap();
+        history
+            .record(2022, 7, Part::A, SubmitOutcome::RateLimited, "2000")
+            .unwrap();
+
+        // Reload to make sure the file round-trips
+        let history = GuessHistory::load(&path).unwrap();
+
+        assert_eq!(
+            history.check(2022, 7, Part::A, "abc"),
+            Err(Refusal::KnownWrong)
+        );
+        assert_eq!(
+            history.check(2022, 7, Part::A, "6000"),
+            Err(Refusal::NotBelow(5000))
+        );
+        assert_eq!(
+            history.check(2022, 7, Part::A, "999"),
+            Err(Refusal::NotAbove(1000))
+        );
+        assert_eq!(history.check(2022, 7, Part::A, "2000"), Ok(()));
+        assert_eq!(history.check(2022, 7, Part::B, "5000"), Ok(()));
+    }
+
+    #[test]
+    fn refuses_solved_parts() {
+        let dir = temp_dir("guesses-solved");
+        fs::create_dir_all(&dir).unwrap();
+
+        let mut history = GuessHistory::load(dir.join("guesses.tsv")).unwrap();
+        history
+            .record(2022, 1, Part::B, SubmitOutcome::Correct, "45000")
+            .unwrap();
+
+        assert_eq!(
+            history.check(2022, 1, Part::B, "45001"),
+            Err(Refusal::AlreadySolved("45000".to_string()))
+        );
+    }
+
+    #[test]
+    fn parses_guesses_by_year() {
+        let guess = parse_guess("1700000000\t2023\t5\ta\twrong\t12").unwrap();
+        assert_eq!(
+            (guess.year, guess.day, guess.outcome),
+            (2023, 5, SubmitOutcome::Wrong)
+        );
+        assert_eq!(parse_guess("1670000000\t5\tb\tcorrect\tMGDMPSZTM"), None);
+    }
+}
@@ -0,0 +1,98 @@
+//! Just enough HTML handling for the puzzle site's pages, which are simple and well-formed.
+
+/// Inner HTML of every `<tag ...>...</tag>` element, in document order. Elements of the same
+/// type must not be nested.
+pub fn elements<'a>(html: &'a str, tag: &str) -> Vec<&'a str> {
+    let open = format!("<{}", tag);
+    let close = format!("</{}>", tag);
+
+    let mut elements = Vec::new();
+    let mut rest = html;
+
+    while let Some(start) = rest.find(&open) {
+        let after_name = &rest[start + open.len()..];
+
+        // Make sure we matched the whole tag name, i.e. `<em>` and not `<emph>`
+        if !after_name.starts_with(['>', ' ', '\t', '\n', '/']) {
+            rest = after_name;
+            continue;
+        }
+
+        let Some(content_start) = after_name.find('>') else {
+            break;
+        };
+        let content = &after_name[content_start + 1..];
+
+        let Some(end) = content.find(&close) else {
+            break;
+        };
+
+        elements.push(&content[..end]);
+        rest = &content[end + close.len()..];
+    }
+
+    elements
+}
+
+/// Strips tags and decodes entities, keeping whitespace as is.
+pub fn text_content(fragment: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+
+    for ch in fragment.chars() {
+        match ch {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            ch if !in_tag => text.push(ch),
+            _ => {}
+        }
+    }
+
+    decode_entities(&text)
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let entity = rest
+            .find(';')
+            .filter(|end| *end <= 10)
+            .and_then(|end| Some((decode_entity(&rest[1..end])?, end)));
+
+        match entity {
+            Some((ch, end)) => {
+                decoded.push(ch);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    decoded.push_str(rest);
+    decoded
+}
+
+fn decode_entity(name: &str) -> Option<char> {
+    match name {
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "amp" => Some('&'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        _ => {
+            let code = match name.strip_prefix("#x") {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => name.strip_prefix('#')?.parse().ok()?,
+            };
+            char::from_u32(code)
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use log::LevelFilter;
+
+/// Sets up logging to stderr. Warnings and errors are shown by default, each `-v` enables one
+/// more level. `directives` use the `RUST_LOG` syntax, but targets may be given as bare module
+/// names, e.g. `day16b=trace` for that day of the given year or `search=debug`.
+pub fn init(verbosity: u8, directives: &[String], year: u16) {
+    let level = match verbosity {
+        0 => LevelFilter::Warn,
+        1 => LevelFilter::Info,
+        2 => LevelFilter::Debug,
+        _ => LevelFilter::Trace,
+    };
+
+    let mut builder = env_logger::Builder::new();
+    builder.filter_level(level).format_timestamp(None);
+
+    if let Ok(filters) = std::env::var("RUST_LOG") {
+        builder.parse_filters(&filters);
+    }
+
+    for directive in directives {
+        builder.parse_filters(&qualify_directive(directive, year));
+    }
+
+    builder.init();
+}
+
+fn qualify_directive(directive: &str, year: u16) -> String {
+    let krate = env!("CARGO_CRATE_NAME");
+
+    directive
+        .split(',')
+        .map(|part| match part.split_once('=') {
+            Some((target, level)) if target.starts_with("day") => {
+                format!("{}::y{}::{}={}", krate, year, target, level)
+            }
+            Some((target, level)) if !target.contains("::") => {
+                format!("{}::{}={}", krate, target, level)
+            }
+            _ => part.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
@@ -1,43 +1,584 @@
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use clap::{Args, Parser, Subcommand};
+use guesses::GuessHistory;
+use perf::PerfHistory;
+use solution::{Limited, Part, Year};
+
+mod cancel;
+mod client;
+mod debugger;
+mod fixtures;
+mod fuzz;
+mod guesses;
+mod html;
+mod logging;
+mod parse;
+mod perf;
+mod search;
+mod snapshot;
+mod solution;
+mod status;
 #[allow(dead_code)]
 mod util;
+mod watch;
 
-#[allow(dead_code)]
-mod day1;
-#[allow(dead_code)]
-mod day10;
-#[allow(dead_code)]
-mod day11;
-#[allow(dead_code)]
-mod day12;
-#[allow(dead_code)]
-mod day13;
-#[allow(dead_code)]
-mod day14;
-#[allow(dead_code)]
-mod day15;
-#[allow(dead_code)]
-mod day2;
-#[allow(dead_code)]
-mod day3;
-#[allow(dead_code)]
-mod day4;
-#[allow(dead_code)]
-mod day5;
-#[allow(dead_code)]
-mod day6;
-#[allow(dead_code)]
-mod day7;
-#[allow(dead_code)]
-mod day8;
-#[allow(dead_code)]
-mod day9;
+mod y2022;
 
-#[allow(dead_code)]
-mod day16a;
-#[allow(dead_code)]
-mod day16b;
+#[derive(Parser)]
+#[command(about = "Advent of Code solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+    /// Show more diagnostics on stderr, repeat for more detail
+    #[arg(short, long, action = clap::ArgAction::Count, global = true)]
+    verbose: u8,
+    /// Log filter per target, e.g. `day16b=trace`
+    #[arg(long, global = true)]
+    log: Vec<String>,
+    /// Year of the puzzles to work with
+    #[arg(long, global = true, default_value_t = solution::latest_year())]
+    year: u16,
+}
+
+#[derive(Args)]
+struct SiteArgs {
+    /// Site to talk to, e.g. a local stand-in server
+    #[arg(long, env = "AOC_BASE_URL", default_value = client::DEFAULT_BASE_URL)]
+    base_url: String,
+    /// Session cookie; read from .aoc-session when not given
+    #[arg(long, env = "AOC_SESSION", hide_env_values = true)]
+    session: Option<String>,
+}
+
+impl SiteArgs {
+    fn client(self) -> Result<client::Client, client::ClientError> {
+        let session = client::resolve_session(self.session)?;
+        Ok(client::Client::new(&self.base_url, session))
+    }
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run a day's solutions, or the latest one if no day is given
+    Run {
+        day: Option<u8>,
+        #[arg(value_enum)]
+        part: Option<Part>,
+        /// Stop each part after this many seconds and show its best answer so far
+        #[arg(long, value_parser = parse_seconds)]
+        timeout: Option<Duration>,
+        /// Show the intermediate data the day's solutions work with
+        #[arg(long)]
+        explain: bool,
+    },
+    /// Re-run a day whenever its input or source changes
+    Watch {
+        day: u8,
+        #[arg(value_enum)]
+        part: Option<Part>,
+        /// How often to check for changes, in milliseconds
+        #[arg(long, default_value_t = 500)]
+        interval: u64,
+    },
+    /// Step through the simulation of day 5, 9, 10, 11 or 14
+    Debug {
+        day: u8,
+        #[arg(value_enum, default_value = "a")]
+        part: Part,
+        /// Input to load instead of the day's puzzle input
+        #[arg(long)]
+        input: Option<PathBuf>,
+    },
+    /// Download a day's puzzle input unless it is already on disk
+    Fetch {
+        day: u8,
+        #[command(flatten)]
+        site: SiteArgs,
+    },
+    /// Save the examples and their answers from a downloaded puzzle page as test fixtures
+    Examples {
+        day: u8,
+        /// The puzzle description saved as HTML
+        page: PathBuf,
+        /// Use the n:th code block of the page as the example instead of each part's first
+        #[arg(long)]
+        block: Option<usize>,
+    },
+    /// Submit an answer, computing it with the solution if it isn't given
+    Submit {
+        day: u8,
+        #[arg(value_enum)]
+        part: Part,
+        answer: Option<String>,
+        #[command(flatten)]
+        site: SiteArgs,
+    },
+    /// Run the parsers on random and mutated inputs to check that they never panic
+    Fuzz {
+        /// Targets to fuzz, e.g. `day13` or `day16a`; all of them if none are given
+        targets: Vec<String>,
+        #[arg(long, default_value_t = 100_000)]
+        iterations: usize,
+        /// Seed for generating the inputs; random if not given
+        #[arg(long)]
+        seed: Option<u64>,
+    },
+    /// Review the renderings that no longer match their snapshots, see `cargo test`
+    Snapshots {
+        /// Accept every change without asking
+        #[arg(long, conflicts_with = "reject_all")]
+        accept_all: bool,
+        /// Reject every change without asking
+        #[arg(long)]
+        reject_all: bool,
+    },
+    /// Show which days are solved, verified and how fast, for the whole calendar
+    Status {
+        /// Run the solutions and check their answers against the stored correct ones
+        #[arg(long)]
+        verify: bool,
+        /// Time limit per part when verifying, in seconds
+        #[arg(long, value_parser = parse_seconds, default_value = "10")]
+        timeout: Duration,
+    },
+    /// Benchmark the solutions and track their timings over time
+    Perf {
+        #[command(subcommand)]
+        command: PerfCommand,
+    },
+}
+
+#[derive(Subcommand)]
+enum PerfCommand {
+    /// Time the given days, or all of them, and append the timings to the history
+    Run {
+        days: Vec<u8>,
+        /// Run each part this many times and keep the fastest
+        #[arg(long, default_value_t = 1)]
+        repeat: usize,
+    },
+    /// Compare the latest run against the previous one or a baseline
+    Compare {
+        /// Compare against the latest run of this commit instead of the previous run
+        #[arg(long)]
+        baseline: Option<String>,
+        /// Slowdown in percent that counts as a regression
+        #[arg(long, default_value_t = 10.0)]
+        threshold: f64,
+    },
+}
+
+fn parse_seconds(s: &str) -> Result<Duration, String> {
+    s.parse()
+        .ok()
+        .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+        .ok_or_else(|| format!("`{}` isn't a number of seconds", s))
+}
+
+type CliResult<T = ()> = Result<T, Box<dyn std::error::Error>>;
+
+fn run(
+    year: &'static Year,
+    day: Option<u8>,
+    part: Option<Part>,
+    timeout: Option<Duration>,
+    explain: bool,
+) -> CliResult {
+    let solutions = match day {
+        None => vec![year.latest()],
+        Some(day) => year.select(day, part),
+    };
+
+    if solutions.is_empty() {
+        return Err(format!("no solution for day {}", day.unwrap_or_default()).into());
+    }
+
+    if explain {
+        let day = solutions[0].day;
+        let input = solutions[0].read_input()?;
+
+        match (year.explain)(day, &input) {
+            Some(explanation) => println!("Day {} explained:\n{}\n", day, explanation?),
+            None => println!("Day {} has nothing to explain\n", day),
+        }
+    }
+
+    for solution in solutions {
+        let input = solution.read_input()?;
+
+        let Some(timeout) = timeout else {
+            let run = solution.run(&input)?;
+            solution::print_answer(solution, &run.answer);
+            continue;
+        };
+
+        match solution.run_with_timeout(input, timeout)? {
+            Limited::Finished(run) => solution::print_answer(solution, &run.answer),
+            Limited::TimedOut(partial) => {
+                let answer = match partial {
+                    Some(partial) => {
+                        format!("timed out after {:?}, best so far {}", timeout, partial)
+                    }
+                    None => format!("timed out after {:?}", timeout),
+                };
+                solution::print_answer(solution, &answer);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn debug(year: &'static Year, day: u8, part: Part, input: Option<PathBuf>) -> CliResult {
+    let solution = year
+        .find(day, part)
+        .ok_or_else(|| format!("no solution for day {}{}", day, part))?;
+
+    let input = match input {
+        Some(path) => std::fs::read_to_string(path)?,
+        None => solution.read_input()?,
+    };
+
+    (year.debug)(solution, &input)
+}
+
+fn fetch(year: &Year, day: u8, site: SiteArgs) -> CliResult {
+    let cache = client::InputCache::default();
+
+    if cache.path(year.year, day).exists() {
+        println!("Day {} input already cached", day);
+        return Ok(());
+    }
+
+    let (path, _) = cache.get_or_fetch(&site.client()?, year.year, day)?;
+    println!("Day {} input saved to {}", day, path.display());
+
+    Ok(())
+}
+
+fn examples(year: &Year, day: u8, page: &Path, block: Option<usize>) -> CliResult {
+    let page = std::fs::read_to_string(page)?;
+    let examples = fixtures::extract(&page, block);
+
+    if examples.is_empty() {
+        return Err("no examples with answers found on the page".into());
+    }
+
+    for example in &examples {
+        println!(
+            "Day {}{}: {} line example, answer {}",
+            day,
+            example.part,
+            example.input.lines().count(),
+            example.answer
+        );
+    }
+
+    for path in fixtures::save(&fixtures::default_dir(), year.year, day, &examples)? {
+        println!("Wrote {}", path.display());
+    }
+
+    Ok(())
+}
+
+fn submit(
+    year: &'static Year,
+    day: u8,
+    part: Part,
+    answer: Option<String>,
+    site: SiteArgs,
+) -> CliResult {
+    let answer = match answer {
+        Some(answer) => answer,
+        None => {
+            let solution = year
+                .find(day, part)
+                .ok_or_else(|| format!("no solution for day {}{}", day, part))?;
+            let run = solution.run(&solution.read_input()?)?;
+            solution::print_answer(solution, &run.answer);
+            run.answer
+        }
+    };
+
+    let answer = answer.trim();
+
+    if answer.is_empty() || answer.contains('\n') {
+        return Err("answer must be a single non-empty line".into());
+    }
+
+    let mut history = GuessHistory::load(GuessHistory::default_path())?;
+    history.check(year.year, day, part, answer)?;
+
+    let (outcome, message) = site
+        .client()?
+        .submit_answer(year.year, day, part.level(), answer)?;
+    history.record(year.year, day, part, outcome, answer)?;
+
+    println!("{}: {}", outcome.name(), message);
+
+    Ok(())
+}
+
+fn fuzz(year: &Year, targets: Vec<String>, iterations: usize, seed: Option<u64>) -> CliResult {
+    let targets = if targets.is_empty() {
+        year.fuzz_targets.iter().collect()
+    } else {
+        targets
+            .iter()
+            .map(|name| {
+                year.fuzz_targets
+                    .iter()
+                    .find(|target| target.name == name)
+                    .ok_or_else(|| format!("no fuzz target {}", name))
+            })
+            .collect::<Result<Vec<_>, _>>()?
+    };
+
+    let seed = seed.unwrap_or_else(|| {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_nanos() as u64)
+    });
+
+    // The panics are reported below, the default hook would only add noise
+    std::panic::set_hook(Box::new(|_| {}));
+
+    for target in targets {
+        match fuzz::fuzz(target, iterations, seed) {
+            Ok(stats) => println!(
+                "{}: ok, {} accepted, {} rejected",
+                target.name, stats.accepted, stats.rejected
+            ),
+            Err(crash) => {
+                let _ = std::panic::take_hook();
+
+                let dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+                    .join("target")
+                    .join("fuzz");
+                let path = dir.join(format!("{}-{}.crash", target.name, seed));
+                std::fs::create_dir_all(&dir)?;
+                std::fs::write(&path, &crash.input)?;
+
+                return Err(format!(
+                    "{} panicked: {} (input saved to {}, seed {})",
+                    target.name,
+                    crash.message,
+                    path.display(),
+                    seed
+                )
+                .into());
+            }
+        }
+    }
+
+    let _ = std::panic::take_hook();
+
+    Ok(())
+}
+
+fn snapshots(accept_all: bool, reject_all: bool) -> CliResult {
+    let pending = snapshot::pending(&snapshot::default_dir())?;
+
+    if pending.is_empty() {
+        println!("No snapshot changes to review");
+        return Ok(());
+    }
+
+    let stdin = std::io::stdin();
+
+    for change in pending {
+        let accept = if accept_all || reject_all {
+            accept_all
+        } else {
+            let status = if change.old.is_some() {
+                "changed"
+            } else {
+                "is new"
+            };
+            println!("{} {}:", change.name, status);
+            print!(
+                "{}",
+                snapshot::diff(change.old.as_deref().unwrap_or(""), &change.new)
+            );
+
+            print!("Accept? [y/n/s(kip)] ");
+            std::io::Write::flush(&mut std::io::stdout())?;
+
+            let mut line = String::new();
+            if stdin.read_line(&mut line)? == 0 {
+                break;
+            }
+
+            match line.trim() {
+                "y" => true,
+                "n" => false,
+                _ => continue,
+            }
+        };
+
+        if accept {
+            change.accept()?;
+            println!("Accepted {}", change.name);
+        } else {
+            change.reject()?;
+            println!("Rejected {}", change.name);
+        }
+    }
+
+    Ok(())
+}
+
+fn status(year: &Year, verify: bool, timeout: Duration) -> CliResult {
+    let statuses = status::collect(
+        year,
+        &client::InputCache::default(),
+        &GuessHistory::load(GuessHistory::default_path())?,
+        &PerfHistory::load(PerfHistory::default_path())?,
+        verify.then_some(timeout),
+    );
+
+    println!("{}", status::render(&statuses));
+
+    Ok(())
+}
+
+fn perf_run(year: &Year, days: Vec<u8>, repeat: usize) -> CliResult {
+    let solutions = year
+        .solutions
+        .iter()
+        .filter(|solution| days.is_empty() || days.contains(&solution.day))
+        .collect::<Vec<_>>();
+
+    if solutions.is_empty() {
+        return Err("no solutions for the given days".into());
+    }
+
+    let commit = perf::current_commit();
+    let mut timings = Vec::new();
+
+    for solution in solutions {
+        let input = solution.read_input()?;
+        let elapsed = perf::benchmark(solution, &input, repeat)?;
+        println!("Day {}{}: {:?}", solution.day, solution.part, elapsed);
+        timings.push((solution.year, solution.day, solution.part, elapsed));
+    }
+
+    let mut history = PerfHistory::load(PerfHistory::default_path())?;
+    history.record(&commit, &timings)?;
+    println!("Recorded run of {}", commit);
+
+    Ok(())
+}
+
+fn perf_compare(baseline: Option<String>, threshold: f64) -> CliResult {
+    let history = PerfHistory::load(PerfHistory::default_path())?;
+    let runs = history.runs();
+
+    let Some((latest, earlier)) = runs.split_last() else {
+        return Err("no benchmark runs recorded yet, see `perf run`".into());
+    };
+
+    let old = match &baseline {
+        Some(commit) => earlier
+            .iter()
+            .rev()
+            .find(|run| run[0].commit.starts_with(commit.as_str()))
+            .ok_or_else(|| format!("no earlier run of commit {}", commit))?,
+        None => earlier
+            .last()
+            .ok_or("only one benchmark run recorded, nothing to compare")?,
+    };
+
+    println!("{} -> {}", old[0].commit, latest[0].commit);
+
+    let changes = perf::compare(old, latest);
+    let mut regressions = 0;
+
+    for change in &changes {
+        let regressed = change.is_regression(threshold);
+        regressions += regressed as usize;
+
+        println!(
+            "Day {}{}: {:?} -> {:?} ({:+.1}%){}",
+            change.day,
+            change.part,
+            change.old,
+            change.new,
+            change.percent(),
+            if regressed { "  REGRESSED" } else { "" }
+        );
+    }
+
+    if regressions > 0 {
+        return Err(format!(
+            "{} part(s) regressed by more than {}%",
+            regressions, threshold
+        )
+        .into());
+    }
+
+    Ok(())
+}
 
 fn main() {
-    //day16::day16a();
-    day16b::day16b();
+    let cli = Cli::parse();
+    logging::init(cli.verbose, &cli.log, cli.year);
+
+    let Some(year) = solution::year(cli.year) else {
+        eprintln!("error: no solutions for year {}", cli.year);
+        std::process::exit(1);
+    };
+
+    let result = match cli.command {
+        None => run(year, None, None, None, false),
+        Some(Command::Run {
+            day,
+            part,
+            timeout,
+            explain,
+        }) => run(year, day, part, timeout, explain),
+        Some(Command::Watch {
+            day,
+            part,
+            interval,
+        }) => watch::watch(year, day, part, Duration::from_millis(interval)),
+        Some(Command::Debug { day, part, input }) => debug(year, day, part, input),
+        Some(Command::Fetch { day, site }) => fetch(year, day, site),
+        Some(Command::Examples { day, page, block }) => examples(year, day, &page, block),
+        Some(Command::Submit {
+            day,
+            part,
+            answer,
+            site,
+        }) => submit(year, day, part, answer, site),
+        Some(Command::Fuzz {
+            targets,
+            iterations,
+            seed,
+        }) => fuzz(year, targets, iterations, seed),
+        Some(Command::Snapshots {
+            accept_all,
+            reject_all,
+        }) => snapshots(accept_all, reject_all),
+        Some(Command::Status { verify, timeout }) => status(year, verify, timeout),
+        Some(Command::Perf {
+            command: PerfCommand::Run { days, repeat },
+        }) => perf_run(year, days, repeat),
+        Some(Command::Perf {
+            command:
+                PerfCommand::Compare {
+                    baseline,
+                    threshold,
+                },
+        }) => perf_compare(baseline, threshold),
+    };
+
+    if let Err(error) = result {
+        eprintln!("error: {}", error);
+        std::process::exit(1);
+    }
 }
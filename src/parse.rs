@@ -0,0 +1,103 @@
+//! Error type and helpers shared by the days' input parsers. Parsers return errors instead of
+//! panicking, so that they can be run on arbitrary input.
+
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line of the input the error was found on, if known
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            line: None,
+            message: message.into(),
+        }
+    }
+
+    /// Attributes the error to a line, unless it already has one.
+    pub fn at_line(mut self, line: usize) -> Self {
+        self.line.get_or_insert(line);
+        self
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {}: {}", line, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+pub type ParseResult<T> = Result<T, ParseError>;
+
+/// Parses every line with `f`, attributing errors to the line they came from.
+pub fn parse_lines<'a, T>(
+    input: &'a str,
+    mut f: impl FnMut(&'a str) -> ParseResult<T>,
+) -> ParseResult<Vec<T>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| f(line).map_err(|error| error.at_line(i + 1)))
+        .collect()
+}
+
+/// Parses every blank line separated block with `f`. Line numbers in the errors of `f` are
+/// relative to the block and get turned into line numbers of the whole input.
+pub fn parse_blocks<'a, T>(
+    input: &'a str,
+    mut f: impl FnMut(&'a str) -> ParseResult<T>,
+) -> ParseResult<Vec<T>> {
+    let mut first_line = 1;
+
+    input
+        .split("\n\n")
+        .map(|block| {
+            let result = f(block).map_err(|mut error| {
+                error.line = Some(first_line + error.line.map_or(0, |line| line - 1));
+                error
+            });
+
+            first_line += block.lines().count() + 1;
+            result
+        })
+        .collect()
+}
+
+pub fn number<T: FromStr>(s: &str) -> ParseResult<T> {
+    s.parse()
+        .map_err(|_| ParseError::new(format!("invalid number `{}`", s)))
+}
+
+pub fn split_once<'a>(s: &'a str, separator: &str) -> ParseResult<(&'a str, &'a str)> {
+    s.split_once(separator)
+        .ok_or_else(|| ParseError::new(format!("expected `{}` in `{}`", separator, s)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn errors_point_at_input_lines() {
+        let error = parse_lines("1\n2\nx", number::<u32>).unwrap_err();
+        assert_eq!(error.to_string(), "line 3: invalid number `x`");
+
+        let error = parse_blocks("1\n2\n\n3\n\n4\nx", |block| {
+            parse_lines(block, number::<u32>)
+        })
+        .unwrap_err();
+        assert_eq!(error.line, Some(7));
+    }
+}
@@ -0,0 +1,300 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process::Command,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    parse::ParseResult,
+    solution::{Part, Solution},
+};
+
+/// The timing of one part in one benchmark run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Timing {
+    pub timestamp: u64,
+    pub commit: String,
+    pub year: u16,
+    pub day: u8,
+    pub part: Part,
+    pub elapsed: Duration,
+}
+
+/// Every benchmark run, stored as one tab-separated line per timed part:
+/// `timestamp commit year day part nanoseconds`. The timings of a run share the timestamp. Lines
+/// from before the year column count as 2022.
+pub struct PerfHistory {
+    path: PathBuf,
+    timings: Vec<Timing>,
+}
+
+impl PerfHistory {
+    pub fn default_path() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("perf.tsv")
+    }
+
+    pub fn load(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(error) => return Err(error),
+        };
+
+        let timings = contents
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.is_empty())
+            .map(|(i, line)| {
+                parse_timing(line).ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("{}:{}: malformed timing", path.display(), i + 1),
+                    )
+                })
+            })
+            .collect::<io::Result<_>>()?;
+
+        Ok(Self { path, timings })
+    }
+
+    /// The runs in the order they were recorded.
+    pub fn runs(&self) -> Vec<&[Timing]> {
+        self.timings
+            .chunk_by(|a, b| a.timestamp == b.timestamp && a.commit == b.commit)
+            .collect()
+    }
+
+    /// The most recent timing of the part.
+    pub fn latest(&self, year: u16, day: u8, part: Part) -> Option<&Timing> {
+        self.timings
+            .iter()
+            .rfind(|timing| timing.year == year && timing.day == day && timing.part == part)
+    }
+
+    pub fn record(
+        &mut self,
+        commit: &str,
+        timings: &[(u16, u8, Part, Duration)],
+    ) -> io::Result<()> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_secs());
+
+        // Keep runs apart even if two of them start within the same second
+        let timestamp = match self.timings.last() {
+            Some(last) if last.timestamp >= timestamp => last.timestamp + 1,
+            _ => timestamp,
+        };
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+
+        for &(year, day, part, elapsed) in timings {
+            let timing = Timing {
+                timestamp,
+                commit: commit.to_string(),
+                year,
+                day,
+                part,
+                elapsed,
+            };
+
+            writeln!(
+                file,
+                "{}\t{}\t{}\t{}\t{}\t{}",
+                timing.timestamp,
+                timing.commit,
+                timing.year,
+                timing.day,
+                timing.part,
+                timing.elapsed.as_nanos()
+            )?;
+
+            self.timings.push(timing);
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_timing(line: &str) -> Option<Timing> {
+    let legacy = line.split('\t').count() == 5;
+    let mut fields = line.split('\t');
+
+    let timestamp = fields.next()?.parse().ok()?;
+    let commit = fields.next()?.to_string();
+    let year = if legacy {
+        2022
+    } else {
+        fields.next()?.parse().ok()?
+    };
+    let day = fields.next()?.parse().ok()?;
+    let part = match fields.next()? {
+        "a" => Part::A,
+        "b" => Part::B,
+        _ => return None,
+    };
+    let elapsed = Duration::from_nanos(fields.next()?.parse().ok()?);
+
+    Some(Timing {
+        timestamp,
+        commit,
+        year,
+        day,
+        part,
+        elapsed,
+    })
+}
+
+/// The checked out commit, marked dirty if there are uncommitted changes, or "unknown" outside
+/// of a git checkout.
+pub fn current_commit() -> String {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .current_dir(env!("CARGO_MANIFEST_DIR"))
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+
+    let Some(commit) = git(&["rev-parse", "--short", "HEAD"]) else {
+        return "unknown".to_string();
+    };
+
+    let dirty = git(&["status", "--porcelain"]).is_some_and(|status| !status.is_empty());
+
+    if dirty {
+        format!("{}-dirty", commit)
+    } else {
+        commit
+    }
+}
+
+/// Runs each solution `repeat` times and keeps the fastest time, which is the least noisy.
+pub fn benchmark(solution: &Solution, input: &str, repeat: usize) -> ParseResult<Duration> {
+    let mut fastest = solution.run(input)?.elapsed;
+
+    for _ in 1..repeat {
+        fastest = fastest.min(solution.run(input)?.elapsed);
+    }
+
+    Ok(fastest)
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Change {
+    pub year: u16,
+    pub day: u8,
+    pub part: Part,
+    pub old: Duration,
+    pub new: Duration,
+}
+
+impl Change {
+    /// Relative change in percent, positive if the part got slower.
+    pub fn percent(&self) -> f64 {
+        (self.new.as_secs_f64() / self.old.as_secs_f64() - 1.0) * 100.0
+    }
+
+    /// Whether the part got slower by more than `threshold` percent. Sub-millisecond parts are
+    /// dominated by noise, so they never count as regressed.
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.new >= Duration::from_millis(1) && self.percent() > threshold
+    }
+}
+
+/// Pairs up the parts timed in both runs.
+pub fn compare(old: &[Timing], new: &[Timing]) -> Vec<Change> {
+    new.iter()
+        .filter_map(|new| {
+            let old = old
+                .iter()
+                .find(|old| old.year == new.year && old.day == new.day && old.part == new.part)?;
+
+            Some(Change {
+                year: new.year,
+                day: new.day,
+                part: new.part,
+                old: old.elapsed,
+                new: new.elapsed,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::tests::temp_dir;
+
+    #[test]
+    fn runs_round_trip() {
+        let dir = temp_dir("perf");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("perf.tsv");
+
+        let mut history = PerfHistory::load(&path).unwrap();
+        history
+            .record(
+                "abc1234",
+                &[
+                    (2022, 1, Part::A, Duration::from_micros(50)),
+                    (2022, 1, Part::B, Duration::from_micros(70)),
+                ],
+            )
+            .unwrap();
+        history
+            .record(
+                "def5678-dirty",
+                &[(2022, 1, Part::A, Duration::from_micros(40))],
+            )
+            .unwrap();
+
+        let history = PerfHistory::load(&path).unwrap();
+        let runs = history.runs();
+
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[0].len(), 2);
+        assert_eq!(runs[1][0].commit, "def5678-dirty");
+        assert_eq!(runs[1][0].elapsed, Duration::from_micros(40));
+        assert_eq!(
+            history.latest(2022, 1, Part::A).unwrap().commit,
+            "def5678-dirty"
+        );
+        assert!(history.latest(2023, 1, Part::A).is_none());
+    }
+
+    #[test]
+    fn flags_regressions_beyond_threshold() {
+        let timing = |day, part, millis| Timing {
+            timestamp: 0,
+            commit: String::new(),
+            year: 2022,
+            day,
+            part,
+            elapsed: Duration::from_millis(millis),
+        };
+
+        let old = [timing(15, Part::B, 100), timing(16, Part::B, 100)];
+        let new = [
+            timing(15, Part::B, 105),
+            timing(16, Part::B, 150),
+            timing(17, Part::A, 10),
+        ];
+
+        let changes = compare(&old, &new);
+
+        assert_eq!(changes.len(), 2);
+        assert!(!changes[0].is_regression(10.0));
+        assert!(changes[1].is_regression(10.0));
+        assert!(!changes[1].is_regression(60.0));
+    }
+}
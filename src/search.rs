@@ -0,0 +1,516 @@
+//! Graph searches over implicit graphs, where the nodes are generated on the fly by a successor
+//! function, and a parallel branch-and-bound for maximization problems.
+
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, VecDeque},
+    fmt::{self, Display, Formatter},
+    hash::Hash,
+    ops::Add,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use dashmap::DashMap;
+use fnv::{FnvBuildHasher, FnvHashMap};
+use rayon::prelude::*;
+
+use crate::cancel::Token;
+
+/// Node table shared by the graph searches. Nodes are referred to by their index, and each one
+/// remembers the node it was reached from so that the path can be rebuilt at the end.
+struct Visited<N> {
+    indices: FnvHashMap<N, usize>,
+    nodes: Vec<(N, usize)>,
+}
+
+impl<N: Clone + Hash + Eq> Visited<N> {
+    fn new(start: N) -> Self {
+        let mut indices = FnvHashMap::default();
+        indices.insert(start.clone(), 0);
+
+        Self {
+            indices,
+            nodes: vec![(start, usize::MAX)],
+        }
+    }
+
+    fn path_to(&self, mut index: usize) -> Vec<N> {
+        let mut path = Vec::new();
+
+        while index != usize::MAX {
+            let (node, parent) = &self.nodes[index];
+            path.push(node.clone());
+            index = *parent;
+        }
+
+        path.reverse();
+        path
+    }
+}
+
+/// Breadth-first search from `start` to the first node satisfying `success`. Returns the path
+/// including both ends.
+///
+/// `successors` pushes the neighbors of a node into the given buffer, which is cleared before
+/// each call and reused between them.
+pub fn bfs<N, FN, FS>(start: N, mut successors: FN, mut success: FS) -> Option<Vec<N>>
+where
+    N: Clone + Hash + Eq,
+    FN: FnMut(&N, &mut Vec<N>),
+    FS: FnMut(&N) -> bool,
+{
+    let mut visited = Visited::new(start);
+    let mut queue = VecDeque::from([0]);
+    let mut buffer = Vec::new();
+
+    while let Some(index) = queue.pop_front() {
+        let node = visited.nodes[index].0.clone();
+
+        if success(&node) {
+            return Some(visited.path_to(index));
+        }
+
+        buffer.clear();
+        successors(&node, &mut buffer);
+
+        for neighbor in buffer.drain(..) {
+            if visited.indices.contains_key(&neighbor) {
+                continue;
+            }
+
+            let neighbor_index = visited.nodes.len();
+            visited.indices.insert(neighbor.clone(), neighbor_index);
+            visited.nodes.push((neighbor, index));
+            queue.push_back(neighbor_index);
+        }
+    }
+
+    None
+}
+
+/// Cheapest path from `start` to the first node satisfying `success`, together with its cost.
+/// `successors` pushes `(neighbor, edge cost)` pairs like in [`bfs`].
+pub fn dijkstra<N, C, FN, FS>(start: N, successors: FN, success: FS) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Hash + Eq,
+    C: Copy + Ord + Default + Add<Output = C>,
+    FN: FnMut(&N, &mut Vec<(N, C)>),
+    FS: FnMut(&N) -> bool,
+{
+    astar(start, successors, |_| C::default(), success)
+}
+
+/// A* search. `heuristic` must never overestimate the remaining cost, otherwise the returned
+/// path may not be the cheapest one.
+pub fn astar<N, C, FN, FH, FS>(
+    start: N,
+    mut successors: FN,
+    mut heuristic: FH,
+    mut success: FS,
+) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Hash + Eq,
+    C: Copy + Ord + Default + Add<Output = C>,
+    FN: FnMut(&N, &mut Vec<(N, C)>),
+    FH: FnMut(&N) -> C,
+    FS: FnMut(&N) -> bool,
+{
+    let mut queue = BinaryHeap::new();
+    queue.push(Reverse((heuristic(&start), C::default(), 0)));
+
+    let mut visited = Visited::new(start);
+    let mut costs = vec![C::default()];
+    let mut buffer = Vec::new();
+
+    while let Some(Reverse((_, cost, index))) = queue.pop() {
+        // Stale entry, the node was reached more cheaply after this was queued
+        if cost > costs[index] {
+            continue;
+        }
+
+        let node = visited.nodes[index].0.clone();
+
+        if success(&node) {
+            return Some((visited.path_to(index), cost));
+        }
+
+        buffer.clear();
+        successors(&node, &mut buffer);
+
+        for (neighbor, edge_cost) in buffer.drain(..) {
+            let neighbor_cost = cost + edge_cost;
+
+            let neighbor_index = match visited.indices.get(&neighbor) {
+                Some(&neighbor_index) if costs[neighbor_index] <= neighbor_cost => continue,
+                Some(&neighbor_index) => {
+                    costs[neighbor_index] = neighbor_cost;
+                    visited.nodes[neighbor_index].1 = index;
+                    neighbor_index
+                }
+                None => {
+                    let neighbor_index = visited.nodes.len();
+                    visited.indices.insert(neighbor.clone(), neighbor_index);
+                    visited.nodes.push((neighbor.clone(), index));
+                    costs.push(neighbor_cost);
+                    neighbor_index
+                }
+            };
+
+            queue.push(Reverse((
+                neighbor_cost + heuristic(&neighbor),
+                neighbor_cost,
+                neighbor_index,
+            )));
+        }
+    }
+
+    None
+}
+
+/// A maximization problem for [`BranchAndBound`]. Scores are absolute: the score of a state is
+/// the best final score of any leaf reachable from it, not the gain over the state itself.
+pub trait Problem: Sync {
+    type State: Clone + Hash + Eq + Send + Sync;
+    type Score: Copy + Ord + Send + Sync + Into<u64>;
+
+    /// The final score of a leaf, or `None` if the state has to be branched further.
+    fn leaf_score(&self, state: &Self::State) -> Option<Self::Score>;
+
+    /// Pushes the successors of a non-leaf state.
+    fn branch(&self, state: &Self::State, out: &mut Vec<Self::State>);
+
+    /// An optimistic estimate of the best score reachable from the state. States whose bound
+    /// can't beat the incumbent are pruned. The default never prunes.
+    fn upper_bound(&self, _state: &Self::State) -> Option<Self::Score> {
+        None
+    }
+}
+
+/// The best score found so far. It can be shared with other threads, e.g. to report progress or
+/// to fall back on if the search is stopped early.
+#[derive(Debug, Default)]
+pub struct Incumbent(AtomicU64);
+
+impl Incumbent {
+    /// Starts from a known score, e.g. of a heuristic solution, so that anything worse is
+    /// pruned from the start.
+    pub fn new(score: u64) -> Self {
+        Self(AtomicU64::new(score))
+    }
+
+    pub fn get(&self) -> u64 {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    fn offer(&self, score: u64) {
+        self.0.fetch_max(score, Ordering::Relaxed);
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Stats {
+    /// States that were branched
+    pub expanded: AtomicU64,
+    pub leaves: AtomicU64,
+    pub pruned: AtomicU64,
+    pub cache_hits: AtomicU64,
+}
+
+impl Stats {
+    fn count(counter: &AtomicU64) {
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} expanded, {} leaves, {} pruned, {} cache hits",
+            self.expanded.load(Ordering::Relaxed),
+            self.leaves.load(Ordering::Relaxed),
+            self.pruned.load(Ordering::Relaxed),
+            self.cache_hits.load(Ordering::Relaxed),
+        )
+    }
+}
+
+/// What is known about the best score under a state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Entry<S> {
+    /// The state was fully explored.
+    Exact(S),
+    /// Parts of the state were pruned, and nothing under it scores more than this.
+    AtMost(S),
+    /// The search was cancelled before the state was explored.
+    Unknown,
+}
+
+impl<S: Ord> Entry<S> {
+    /// Combines the entries of two siblings into one for their parent.
+    fn merge(self, other: Self) -> Self {
+        match (self, other) {
+            (Entry::Unknown, _) | (_, Entry::Unknown) => Entry::Unknown,
+            (Entry::Exact(a), Entry::Exact(b)) => Entry::Exact(a.max(b)),
+            (Entry::Exact(a) | Entry::AtMost(a), Entry::Exact(b) | Entry::AtMost(b)) => {
+                Entry::AtMost(a.max(b))
+            }
+        }
+    }
+}
+
+/// Depth-first branch-and-bound that explores the children of every state in parallel.
+pub struct BranchAndBound<'a, P: Problem> {
+    problem: &'a P,
+    incumbent: &'a Incumbent,
+    cache: Option<DashMap<P::State, Entry<P::Score>, FnvBuildHasher>>,
+    token: Token,
+    stats: Stats,
+}
+
+impl<'a, P: Problem> BranchAndBound<'a, P> {
+    pub fn new(problem: &'a P, incumbent: &'a Incumbent) -> Self {
+        Self {
+            problem,
+            incumbent,
+            cache: None,
+            token: Token::default(),
+            stats: Stats::default(),
+        }
+    }
+
+    /// Remembers the score of every explored state, for problems where the same state can be
+    /// reached along many paths. States with pruned parts are remembered by an upper bound,
+    /// which prunes them again once the incumbent has overtaken it.
+    pub fn memoize(mut self) -> Self {
+        self.cache = Some(DashMap::default());
+        self
+    }
+
+    /// Stops the search once the token is cancelled. The incumbent then holds the best score
+    /// found so far.
+    pub fn cancellable(mut self, token: Token) -> Self {
+        self.token = token;
+        self
+    }
+
+    pub fn stats(&self) -> &Stats {
+        &self.stats
+    }
+
+    /// The best score reachable from `initial`, or `None` if every leaf was pruned.
+    pub fn solve(&self, initial: P::State) -> Option<P::Score> {
+        let (score, _) = self.search(&initial);
+        score
+    }
+
+    /// Returns the best score found under the state, and what is known about the best score
+    /// overall. The two differ when parts of the state were pruned against the incumbent.
+    fn search(&self, state: &P::State) -> (Option<P::Score>, Entry<Option<P::Score>>) {
+        if self.token.is_cancelled() {
+            return (None, Entry::Unknown);
+        }
+
+        if let Some(score) = self.problem.leaf_score(state) {
+            Stats::count(&self.stats.leaves);
+            self.incumbent.offer(score.into());
+            return (Some(score), Entry::Exact(Some(score)));
+        }
+
+        let cached = self
+            .cache
+            .as_ref()
+            .and_then(|cache| cache.get(state).map(|entry| *entry));
+
+        match cached {
+            Some(Entry::Exact(score)) => {
+                Stats::count(&self.stats.cache_hits);
+                return (Some(score), Entry::Exact(Some(score)));
+            }
+            Some(Entry::AtMost(bound)) if bound.into() < self.incumbent.get() => {
+                Stats::count(&self.stats.cache_hits);
+                return (None, Entry::AtMost(Some(bound)));
+            }
+            _ => {}
+        }
+
+        if let Some(bound) = self.problem.upper_bound(state) {
+            if bound.into() < self.incumbent.get() {
+                Stats::count(&self.stats.pruned);
+                return (None, Entry::AtMost(Some(bound)));
+            }
+        }
+
+        Stats::count(&self.stats.expanded);
+
+        let mut children = Vec::new();
+        self.problem.branch(state, &mut children);
+
+        let (score, entry) = children
+            .par_iter()
+            .map(|child| self.search(child))
+            .reduce_with(|(a, a_entry), (b, b_entry)| (a.max(b), a_entry.merge(b_entry)))
+            .unwrap_or((None, Entry::Exact(None)));
+
+        if let Some(cache) = &self.cache {
+            match entry {
+                Entry::Exact(Some(score)) => {
+                    cache.insert(state.clone(), Entry::Exact(score));
+                }
+                Entry::AtMost(Some(bound)) => {
+                    cache.insert(state.clone(), Entry::AtMost(bound));
+                }
+                _ => {}
+            }
+        }
+
+        (score, entry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GRID: &str = "\
+S..#....
+.#.#.##.
+.#...#..
+.####.#.
+......#E";
+
+    fn grid_neighbors(pos: &(usize, usize), out: &mut Vec<(usize, usize)>) {
+        let rows = GRID.lines().map(str::as_bytes).collect::<Vec<_>>();
+        let (x, y) = *pos;
+
+        let candidates = [
+            (x.wrapping_sub(1), y),
+            (x + 1, y),
+            (x, y.wrapping_sub(1)),
+            (x, y + 1),
+        ];
+
+        for (x, y) in candidates {
+            if rows
+                .get(y)
+                .and_then(|row| row.get(x))
+                .is_some_and(|c| *c != b'#')
+            {
+                out.push((x, y));
+            }
+        }
+    }
+
+    #[test]
+    fn graph_searches_agree_on_shortest_path() {
+        let end = (7, 4);
+
+        let path = bfs((0, 0), grid_neighbors, |pos| *pos == end).unwrap();
+        assert_eq!(path.first(), Some(&(0, 0)));
+        assert_eq!(path.last(), Some(&end));
+        assert_eq!(path.len() - 1, 15);
+
+        let weighted = |pos: &(usize, usize), out: &mut Vec<((usize, usize), u32)>| {
+            let mut neighbors = Vec::new();
+            grid_neighbors(pos, &mut neighbors);
+            out.extend(neighbors.into_iter().map(|neighbor| (neighbor, 1)));
+        };
+
+        let (_, cost) = dijkstra((0, 0), weighted, |pos| *pos == end).unwrap();
+        assert_eq!(cost, 15);
+
+        let manhattan =
+            |pos: &(usize, usize)| (end.0.abs_diff(pos.0) + end.1.abs_diff(pos.1)) as u32;
+        let (path, cost) = astar((0, 0), weighted, manhattan, |pos| *pos == end).unwrap();
+        assert_eq!(cost, 15);
+        assert_eq!(path.len(), 16);
+
+        assert_eq!(bfs((0, 0), grid_neighbors, |pos| *pos == (20, 0)), None);
+    }
+
+    #[test]
+    fn dijkstra_prefers_cheaper_longer_path() {
+        // 0 -> 1 -> 2 -> 3 costs 3, 0 -> 3 directly costs 10
+        let successors = |node: &u8, out: &mut Vec<(u8, u32)>| {
+            if *node < 3 {
+                out.push((node + 1, 1));
+            }
+            if *node == 0 {
+                out.push((3, 10));
+            }
+        };
+
+        assert_eq!(
+            dijkstra(0, successors, |node| *node == 3),
+            Some((vec![0, 1, 2, 3], 3))
+        );
+    }
+
+    /// 0/1 knapsack, the state being the index of the next item and the value and weight so far
+    struct Knapsack {
+        items: Vec<(u32, u32)>,
+        capacity: u32,
+        bounded: bool,
+    }
+
+    impl Problem for Knapsack {
+        type State = (usize, u32, u32);
+        type Score = u32;
+
+        fn leaf_score(&self, &(next, value, _): &Self::State) -> Option<u32> {
+            (next == self.items.len()).then_some(value)
+        }
+
+        fn branch(&self, &(next, value, weight): &Self::State, out: &mut Vec<Self::State>) {
+            let (item_value, item_weight) = self.items[next];
+
+            out.push((next + 1, value, weight));
+
+            if weight + item_weight <= self.capacity {
+                out.push((next + 1, value + item_value, weight + item_weight));
+            }
+        }
+
+        fn upper_bound(&self, &(next, value, _): &Self::State) -> Option<u32> {
+            self.bounded
+                .then(|| value + self.items[next..].iter().map(|item| item.0).sum::<u32>())
+        }
+    }
+
+    #[test]
+    fn branch_and_bound_finds_optimum() {
+        let items = vec![(60, 10), (100, 20), (120, 30), (30, 5), (45, 15), (10, 1)];
+
+        for (bounded, memoize) in [(false, false), (true, false), (false, true), (true, true)] {
+            let problem = Knapsack {
+                items: items.clone(),
+                capacity: 50,
+                bounded,
+            };
+            let incumbent = Incumbent::default();
+            let search = BranchAndBound::new(&problem, &incumbent);
+            let search = if memoize { search.memoize() } else { search };
+
+            assert_eq!(search.solve((0, 0, 0)), Some(235));
+            assert_eq!(incumbent.get(), 235);
+            assert!(search.stats().leaves.load(Ordering::Relaxed) > 0);
+        }
+    }
+
+    #[test]
+    fn cancelled_branch_and_bound_stops() {
+        let problem = Knapsack {
+            items: vec![(60, 10), (100, 20), (120, 30)],
+            capacity: 50,
+            bounded: false,
+        };
+        let incumbent = Incumbent::default();
+        let token = Token::default();
+        token.cancel();
+
+        let search = BranchAndBound::new(&problem, &incumbent).cancellable(token);
+
+        assert_eq!(search.solve((0, 0, 0)), None);
+        assert_eq!(search.stats().expanded.load(Ordering::Relaxed), 0);
+    }
+}
@@ -0,0 +1,200 @@
+//! Snapshot tests for the renderings. A snapshot is the accepted rendering, stored as
+//! `snapshots/{name}.snap`. A test whose rendering differs from its snapshot, or that has none
+//! yet, saves the rendering as `{name}.snap.new` and fails. The `snapshots` command then shows
+//! the pending changes to accept or reject.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+pub fn default_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("snapshots")
+}
+
+fn snapshot_path(dir: &Path, name: &str) -> PathBuf {
+    dir.join(format!("{}.snap", name))
+}
+
+fn pending_path(dir: &Path, name: &str) -> PathBuf {
+    dir.join(format!("{}.snap.new", name))
+}
+
+fn read_if_exists(path: &Path) -> io::Result<Option<String>> {
+    match fs::read_to_string(path) {
+        Ok(contents) => Ok(Some(contents)),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(error),
+    }
+}
+
+/// Snapshots end in exactly one newline, whether the rendering has one or not.
+#[cfg(test)]
+fn normalize(rendering: &str) -> String {
+    format!("{}\n", rendering.trim_end_matches('\n'))
+}
+
+/// Compares the rendering with its snapshot. Returns whether they match, saving the rendering
+/// for review if they don't.
+#[cfg(test)]
+pub fn check(dir: &Path, name: &str, rendering: &str) -> io::Result<bool> {
+    let contents = normalize(rendering);
+    let pending = pending_path(dir, name);
+
+    if read_if_exists(&snapshot_path(dir, name))?.as_deref() == Some(&contents) {
+        // A leftover from an earlier failure that has been fixed since
+        if pending.exists() {
+            fs::remove_file(&pending)?;
+        }
+
+        return Ok(true);
+    }
+
+    fs::create_dir_all(dir)?;
+    fs::write(&pending, contents)?;
+
+    Ok(false)
+}
+
+/// Fails the test if the rendering doesn't match its snapshot.
+#[cfg(test)]
+pub fn assert_snapshot(name: &str, rendering: &str) {
+    let dir = default_dir();
+
+    if !check(&dir, name, rendering).unwrap() {
+        let old = read_if_exists(&snapshot_path(&dir, name)).unwrap();
+
+        panic!(
+            "snapshot `{}` changed, review it with `snapshots`:\n{}",
+            name,
+            diff(old.as_deref().unwrap_or(""), &normalize(rendering))
+        );
+    }
+}
+
+/// A rendering waiting to be accepted or rejected.
+#[derive(Debug)]
+pub struct Pending {
+    pub name: String,
+    /// The accepted snapshot, if there is one yet
+    pub old: Option<String>,
+    pub new: String,
+    dir: PathBuf,
+}
+
+impl Pending {
+    pub fn accept(&self) -> io::Result<()> {
+        fs::rename(
+            pending_path(&self.dir, &self.name),
+            snapshot_path(&self.dir, &self.name),
+        )
+    }
+
+    pub fn reject(&self) -> io::Result<()> {
+        fs::remove_file(pending_path(&self.dir, &self.name))
+    }
+}
+
+/// The pending renderings, sorted by name.
+pub fn pending(dir: &Path) -> io::Result<Vec<Pending>> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(error) => return Err(error),
+    };
+
+    let mut pending = Vec::new();
+
+    for entry in entries {
+        let file_name = entry?.file_name();
+        let Some(name) = file_name
+            .to_str()
+            .and_then(|name| name.strip_suffix(".snap.new"))
+        else {
+            continue;
+        };
+
+        pending.push(Pending {
+            name: name.to_string(),
+            old: read_if_exists(&snapshot_path(dir, name))?,
+            new: fs::read_to_string(pending_path(dir, name))?,
+            dir: dir.to_path_buf(),
+        });
+    }
+
+    pending.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(pending)
+}
+
+/// Line by line diff, marking removed lines with `-` and added ones with `+`.
+pub fn diff(old: &str, new: &str) -> String {
+    let old = old.lines().collect::<Vec<_>>();
+    let new = new.lines().collect::<Vec<_>>();
+
+    // Length of the longest common subsequence of old[i..] and new[j..]
+    let mut common = vec![vec![0; new.len() + 1]; old.len() + 1];
+
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i] == new[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let mut output = String::new();
+    let (mut i, mut j) = (0, 0);
+
+    while i < old.len() || j < new.len() {
+        let line = if i < old.len() && j < new.len() && old[i] == new[j] {
+            i += 1;
+            j += 1;
+            format!(" {}", old[i - 1])
+        } else if i < old.len() && (j == new.len() || common[i + 1][j] >= common[i][j + 1]) {
+            i += 1;
+            format!("-{}", old[i - 1])
+        } else {
+            j += 1;
+            format!("+{}", new[j - 1])
+        };
+
+        output.push_str(&line);
+        output.push('\n');
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::tests::temp_dir;
+
+    #[test]
+    fn changed_rendering_waits_for_review() {
+        let dir = temp_dir("snapshot");
+
+        assert!(!check(&dir, "grid", "#.\n.#").unwrap());
+        let changes = pending(&dir).unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].old, None);
+        changes[0].accept().unwrap();
+
+        assert!(check(&dir, "grid", "#.\n.#").unwrap());
+        assert!(!check(&dir, "grid", "#.\n##").unwrap());
+        let changes = pending(&dir).unwrap();
+        assert_eq!(changes[0].old.as_deref(), Some("#.\n.#\n"));
+        changes[0].reject().unwrap();
+
+        assert!(pending(&dir).unwrap().is_empty());
+        assert!(check(&dir, "grid", "#.\n.#").unwrap());
+    }
+
+    #[test]
+    fn diff_marks_changed_lines() {
+        assert_eq!(diff("a\nb\nc\n", "a\nx\nc\nd\n"), " a\n-b\n+x\n c\n+d\n");
+    }
+}
@@ -0,0 +1,195 @@
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+    fs, io, panic,
+    path::PathBuf,
+    sync::mpsc::{self, RecvTimeoutError},
+    thread,
+    time::{Duration, Instant},
+};
+
+use clap::ValueEnum;
+use log::info;
+
+use crate::{cancel, client::InputCache, fuzz, parse::ParseResult, y2022};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, ValueEnum)]
+pub enum Part {
+    A,
+    B,
+}
+
+impl Part {
+    /// The `level` the puzzle site uses for this part.
+    pub fn level(self) -> u8 {
+        match self {
+            Part::A => 1,
+            Part::B => 2,
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::A => write!(f, "a"),
+            Part::B => write!(f, "b"),
+        }
+    }
+}
+
+type Solver = fn(&str) -> ParseResult<String>;
+
+pub struct Solution {
+    pub year: u16,
+    pub day: u8,
+    pub part: Part,
+    pub solve: Solver,
+}
+
+impl Solution {
+    pub const fn new(year: u16, day: u8, part: Part, solve: Solver) -> Self {
+        Self {
+            year,
+            day,
+            part,
+            solve,
+        }
+    }
+
+    pub fn input_path(&self) -> PathBuf {
+        InputCache::default().path(self.year, self.day)
+    }
+
+    pub fn read_input(&self) -> io::Result<String> {
+        let path = self.input_path();
+
+        fs::read_to_string(&path).map_err(|error| {
+            io::Error::new(
+                error.kind(),
+                format!(
+                    "{}: {} (use `fetch {} --year {}` to download it)",
+                    path.display(),
+                    error,
+                    self.day,
+                    self.year
+                ),
+            )
+        })
+    }
+
+    pub fn run(&self, input: &str) -> ParseResult<Run> {
+        let start_time = Instant::now();
+        let answer = (self.solve)(input)?;
+        let elapsed = start_time.elapsed();
+
+        info!("Day {}{} took {:?}", self.day, self.part, elapsed);
+
+        Ok(Run { answer, elapsed })
+    }
+
+    /// Runs the solution on a thread of its own and cancels it after `timeout`. A solver that
+    /// doesn't check for cancellation keeps running in the background until the process exits.
+    pub fn run_with_timeout(
+        &'static self,
+        input: String,
+        timeout: Duration,
+    ) -> ParseResult<Limited> {
+        let token = cancel::Token::default();
+        let (tx, rx) = mpsc::channel();
+
+        let solver = {
+            let token = token.clone();
+
+            thread::spawn(move || {
+                cancel::install(token);
+                let _ = tx.send(self.run(&input));
+            })
+        };
+
+        match rx.recv_timeout(timeout) {
+            Ok(result) => return result.map(Limited::Finished),
+            Err(RecvTimeoutError::Disconnected) => match solver.join() {
+                Err(payload) => panic::resume_unwind(payload),
+                Ok(()) => unreachable!("the solver always sends its result"),
+            },
+            Err(RecvTimeoutError::Timeout) => {}
+        }
+
+        token.cancel();
+
+        // Errors after cancelling only say that the solver stopped without an answer
+        let partial = rx
+            .recv_timeout(CANCEL_GRACE)
+            .ok()
+            .and_then(Result::ok)
+            .map(|run| run.answer);
+
+        Ok(Limited::TimedOut(partial))
+    }
+}
+
+pub struct Run {
+    pub answer: String,
+    pub elapsed: Duration,
+}
+
+/// How long a cancelled solver gets to stop and hand over its best answer so far.
+const CANCEL_GRACE: Duration = Duration::from_secs(1);
+
+pub enum Limited {
+    Finished(Run),
+    /// The best answer so far, if the solver stopped in time and had one
+    TimedOut(Option<String>),
+}
+
+/// Steps through the simulation of the solution's day.
+pub type DebugFn = fn(&Solution, &str) -> Result<(), Box<dyn Error>>;
+
+/// One event's solutions, and the tools that need to know about its days.
+pub struct Year {
+    pub year: u16,
+    pub solutions: &'static [Solution],
+    pub fuzz_targets: &'static [fuzz::Target],
+    /// The day's `--explain` output, or `None` if it has none
+    pub explain: fn(u8, &str) -> Option<ParseResult<String>>,
+    pub debug: DebugFn,
+}
+
+pub const YEARS: &[Year] = &[y2022::YEAR];
+
+pub fn year(year: u16) -> Option<&'static Year> {
+    YEARS.iter().find(|candidate| candidate.year == year)
+}
+
+pub fn latest_year() -> u16 {
+    YEARS.last().unwrap().year
+}
+
+impl Year {
+    pub fn find(&self, day: u8, part: Part) -> Option<&'static Solution> {
+        self.solutions
+            .iter()
+            .find(|solution| solution.day == day && solution.part == part)
+    }
+
+    /// All solutions for the day, or only the given part.
+    pub fn select(&self, day: u8, part: Option<Part>) -> Vec<&'static Solution> {
+        self.solutions
+            .iter()
+            .filter(|solution| solution.day == day && part.is_none_or(|part| solution.part == part))
+            .collect()
+    }
+
+    pub fn latest(&self) -> &'static Solution {
+        self.solutions.last().unwrap()
+    }
+}
+
+pub fn print_answer(solution: &Solution, answer: &str) {
+    if answer.contains('\n') {
+        println!("Day {}{}:\n{}", solution.day, solution.part, answer);
+    } else {
+        println!("Day {}{}: {}", solution.day, solution.part, answer);
+    }
+}
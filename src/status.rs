@@ -0,0 +1,204 @@
+//! The state of every day of the calendar, derived from the solutions in the crate, the inputs
+//! on disk, the guess history and the benchmark history.
+
+use std::time::Duration;
+
+use crate::{
+    client::InputCache,
+    guesses::GuessHistory,
+    perf::PerfHistory,
+    solution::{Limited, Part, Year},
+};
+
+pub const DAYS: u8 = 25;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    /// No correct answer has been submitted for the part
+    Unknown,
+    /// A correct answer is stored, but the solution wasn't run to check it
+    Stored,
+    Verified,
+    /// The solution gives something else than the stored answer
+    Differs(String),
+    /// The solution failed or timed out
+    Failed,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartStatus {
+    pub day: u8,
+    pub part: Part,
+    pub implemented: bool,
+    pub has_input: bool,
+    pub answer: Answer,
+    pub last_runtime: Option<Duration>,
+}
+
+impl PartStatus {
+    /// Whether the part has earned its star.
+    pub fn is_starred(&self) -> bool {
+        self.answer != Answer::Unknown
+    }
+}
+
+/// The status of both parts of every day. With `verify`, every solution that has an input and
+/// a stored answer is run, with the given timeout, and its answer compared.
+pub fn collect(
+    year: &Year,
+    inputs: &InputCache,
+    guesses: &GuessHistory,
+    perf: &PerfHistory,
+    verify: Option<Duration>,
+) -> Vec<PartStatus> {
+    let mut statuses = Vec::new();
+
+    for day in 1..=DAYS {
+        for part in [Part::A, Part::B] {
+            let solution = year.find(day, part);
+            let input = std::fs::read_to_string(inputs.path(year.year, day)).ok();
+            let stored = guesses.correct_answer(year.year, day, part);
+
+            let answer = match (stored, solution, &input, verify) {
+                (None, ..) => Answer::Unknown,
+                (Some(stored), Some(solution), Some(input), Some(timeout)) => {
+                    match solution.run_with_timeout(input.clone(), timeout) {
+                        Ok(Limited::Finished(run)) if run.answer == stored => Answer::Verified,
+                        Ok(Limited::Finished(run)) => Answer::Differs(run.answer),
+                        _ => Answer::Failed,
+                    }
+                }
+                (Some(_), ..) => Answer::Stored,
+            };
+
+            statuses.push(PartStatus {
+                day,
+                part,
+                implemented: solution.is_some(),
+                has_input: input.is_some(),
+                answer,
+                last_runtime: perf
+                    .latest(year.year, day, part)
+                    .map(|timing| timing.elapsed),
+            });
+        }
+    }
+
+    statuses
+}
+
+fn yes_no(value: bool) -> &'static str {
+    if value {
+        "yes"
+    } else {
+        "-"
+    }
+}
+
+fn render_part(status: &PartStatus) -> String {
+    let answer = match &status.answer {
+        Answer::Unknown => "-".to_string(),
+        Answer::Stored => "stored".to_string(),
+        Answer::Verified => "verified".to_string(),
+        Answer::Differs(answer) => format!("WRONG {}", answer),
+        Answer::Failed => "FAILED".to_string(),
+    };
+
+    let runtime = status
+        .last_runtime
+        .map_or("-".to_string(), |runtime| format!("{:.1?}", runtime));
+
+    format!(
+        "{:<5}{:<6}{:<10}{:<11}",
+        yes_no(status.implemented),
+        yes_no(status.has_input),
+        answer,
+        runtime
+    )
+}
+
+/// One line per day with both parts side by side, like the calendar on the site.
+pub fn render(statuses: &[PartStatus]) -> String {
+    let mut lines = vec![
+        format!("{:<9}{:<32}{}", "", "Part a", "Part b"),
+        format!("{:<9}{1}    {1}", "Day", "Code Input Answer    Runtime"),
+    ];
+
+    for day in statuses.chunks(2) {
+        let stars = day
+            .iter()
+            .map(|status| if status.is_starred() { '*' } else { ' ' })
+            .collect::<String>();
+
+        lines.push(
+            format!(
+                "{:>3} {:<5}{}{}",
+                day[0].day,
+                stars,
+                render_part(&day[0]),
+                render_part(&day[1])
+            )
+            .trim_end()
+            .to_string(),
+        );
+    }
+
+    let stars = statuses.iter().filter(|status| status.is_starred()).count();
+    lines.push(format!("\n{}/{} stars", stars, statuses.len()));
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::{client::tests::temp_dir, client::SubmitOutcome, y2022};
+
+    #[test]
+    fn derives_status_from_crate_and_histories() {
+        let dir = temp_dir("status");
+        fs::create_dir_all(dir.join("y2022")).unwrap();
+        fs::write(dir.join("y2022/day1.input"), "1000\n2000\n\n3000\n").unwrap();
+
+        let mut guesses = GuessHistory::load(dir.join("guesses.tsv")).unwrap();
+        guesses
+            .record(2022, 1, Part::A, SubmitOutcome::TooLow, "1")
+            .unwrap();
+        guesses
+            .record(2022, 1, Part::A, SubmitOutcome::Correct, "3000")
+            .unwrap();
+        guesses
+            .record(2022, 1, Part::B, SubmitOutcome::Correct, "1")
+            .unwrap();
+
+        let mut perf = PerfHistory::load(dir.join("perf.tsv")).unwrap();
+        perf.record("abc1234", &[(2022, 1, Part::A, Duration::from_micros(5))])
+            .unwrap();
+
+        let inputs = InputCache::new(&dir);
+        let statuses = collect(
+            &y2022::YEAR,
+            &inputs,
+            &guesses,
+            &perf,
+            Some(Duration::from_secs(10)),
+        );
+
+        assert_eq!(statuses.len(), 50);
+        assert_eq!(statuses[0].answer, Answer::Verified);
+        assert_eq!(statuses[0].last_runtime, Some(Duration::from_micros(5)));
+        assert_eq!(statuses[1].answer, Answer::Differs("6000".to_string()));
+        assert_eq!(statuses[1].last_runtime, None);
+        assert!(statuses[2].implemented && !statuses[2].has_input);
+
+        let day25 = &statuses[49];
+        assert!(!day25.implemented && !day25.has_input && !day25.is_starred());
+
+        let calendar = render(&statuses);
+        assert!(calendar.contains("\n  1 **   yes  yes   verified  5.0µs"));
+        assert!(calendar.contains("\n 25      -    -     -         -          -"));
+        assert!(calendar.ends_with("\n2/50 stars"));
+    }
+}
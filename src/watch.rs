@@ -0,0 +1,264 @@
+use std::{
+    collections::HashMap,
+    error::Error,
+    fs,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    process::Command,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use clap::ValueEnum;
+use log::{error, warn};
+
+use crate::solution::{Part, Solution, Year};
+
+type Answers = HashMap<Part, String>;
+
+/// Re-runs the day whenever its input changes, printing how the answers changed since the
+/// previous run. A change to the source, of the day or anything it uses, can't be picked up by
+/// the running binary, so in that case the crate is rebuilt and the watcher restarts itself. The
+/// last answers are kept on disk, so the comparison carries over the restart.
+///
+/// Only the answers and their diffs go to stdout; progress goes to stderr and failures are
+/// logged.
+pub fn watch(
+    year: &Year,
+    day: u8,
+    part: Option<Part>,
+    interval: Duration,
+) -> Result<(), Box<dyn Error>> {
+    let solutions = year.select(day, part);
+
+    if solutions.is_empty() {
+        return Err(format!("no solution for day {}", day).into());
+    }
+
+    let input_path = solutions[0].input_path();
+    let state_path = state_path(year.year, day);
+
+    let mut previous = load_answers(&state_path);
+    let mut input_stamp = modified(&input_path);
+    let mut source_stamps = scan_sources();
+
+    eprintln!(
+        "Watching {} and {} source file(s), Ctrl-C to stop",
+        input_path.display(),
+        source_stamps.len()
+    );
+
+    run_all(&solutions, &input_path, &mut previous, &state_path);
+
+    loop {
+        thread::sleep(interval);
+
+        let new_source_stamps = scan_sources();
+
+        if new_source_stamps != source_stamps {
+            source_stamps = new_source_stamps;
+            eprintln!("\nSource changed, rebuilding");
+
+            // Only returns if the build failed; keep watching for the fix
+            rebuild_and_restart();
+            continue;
+        }
+
+        let new_input_stamp = modified(&input_path);
+
+        if new_input_stamp != input_stamp {
+            input_stamp = new_input_stamp;
+            eprintln!("\nInput changed, re-running");
+            run_all(&solutions, &input_path, &mut previous, &state_path);
+        }
+    }
+}
+
+fn run_all(solutions: &[&Solution], input_path: &Path, previous: &mut Answers, state_path: &Path) {
+    let input = match fs::read_to_string(input_path) {
+        Ok(input) => input,
+        Err(error) => {
+            error!("{}: {}", input_path.display(), error);
+            return;
+        }
+    };
+
+    for solution in solutions {
+        let run = match panic::catch_unwind(AssertUnwindSafe(|| solution.run(&input))) {
+            Ok(Ok(run)) => run,
+            Ok(Err(error)) => {
+                error!("Day {}{}: {}", solution.day, solution.part, error);
+                continue;
+            }
+            Err(_) => {
+                error!("Day {}{}: panicked", solution.day, solution.part);
+                continue;
+            }
+        };
+
+        let label = format!("Day {}{}", solution.day, solution.part);
+
+        match previous.get(&solution.part) {
+            None => print_answer(&label, &run.answer, &format!("{:?}", run.elapsed)),
+            Some(old) if *old == run.answer => print_answer(
+                &label,
+                &run.answer,
+                &format!("unchanged, {:?}", run.elapsed),
+            ),
+            Some(old) => {
+                println!("{}: changed ({:?})", label, run.elapsed);
+                print_diff(old, &run.answer);
+            }
+        }
+
+        previous.insert(solution.part, run.answer);
+    }
+
+    if let Err(error) = save_answers(state_path, previous) {
+        warn!("{}: {}", state_path.display(), error);
+    }
+}
+
+fn print_answer(label: &str, answer: &str, note: &str) {
+    if answer.contains('\n') {
+        println!("{} ({}):\n{}", label, note, answer);
+    } else {
+        println!("{}: {} ({})", label, answer, note);
+    }
+}
+
+fn print_diff(old: &str, new: &str) {
+    let old_lines = old.lines().collect::<Vec<_>>();
+    let new_lines = new.lines().collect::<Vec<_>>();
+
+    for i in 0..old_lines.len().max(new_lines.len()) {
+        match (old_lines.get(i), new_lines.get(i)) {
+            (Some(old), Some(new)) if old == new => println!("  {}", new),
+            (old, new) => {
+                if let Some(old) = old {
+                    println!("- {}", old);
+                }
+                if let Some(new) = new {
+                    println!("+ {}", new);
+                }
+            }
+        }
+    }
+}
+
+fn rebuild_and_restart() {
+    let manifest_dir = env!("CARGO_MANIFEST_DIR");
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+
+    let mut build = Command::new(cargo);
+    build.arg("build").current_dir(manifest_dir);
+
+    if !cfg!(debug_assertions) {
+        build.arg("--release");
+    }
+
+    match build.status() {
+        Ok(status) if status.success() => {}
+        Ok(_) => {
+            error!("Build failed, waiting for changes");
+            return;
+        }
+        Err(error) => {
+            error!("Failed to run cargo: {}", error);
+            return;
+        }
+    }
+
+    let exe = match std::env::current_exe() {
+        Ok(exe) => exe,
+        Err(error) => {
+            error!("Can't find the current executable: {}", error);
+            return;
+        }
+    };
+
+    let mut restart = Command::new(exe);
+    restart.args(std::env::args_os().skip(1));
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        let error = restart.exec();
+        error!("Failed to restart: {}", error);
+    }
+
+    #[cfg(not(unix))]
+    match restart.status() {
+        Ok(status) => std::process::exit(status.code().unwrap_or(1)),
+        Err(error) => error!("Failed to restart: {}", error),
+    }
+}
+
+/// Every Rust file under `src/` with its modification time. A day depends on shared modules
+/// too, and files can come and go, so the whole tree is listed on every check.
+fn scan_sources() -> Vec<(PathBuf, Option<SystemTime>)> {
+    let mut stamps = Vec::new();
+    let mut directories = vec![Path::new(env!("CARGO_MANIFEST_DIR")).join("src")];
+
+    while let Some(directory) = directories.pop() {
+        let Ok(entries) = fs::read_dir(&directory) else {
+            continue;
+        };
+
+        for path in entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+        {
+            if path.is_dir() {
+                directories.push(path);
+            } else if path.extension().is_some_and(|extension| extension == "rs") {
+                let stamp = modified(&path);
+                stamps.push((path, stamp));
+            }
+        }
+    }
+
+    stamps.sort();
+    stamps
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+fn state_path(year: u16, day: u8) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("target")
+        .join("watch")
+        .join(format!("{}-day{}.answers", year, day))
+}
+
+fn load_answers(path: &Path) -> Answers {
+    let Ok(contents) = fs::read_to_string(path) else {
+        return Answers::new();
+    };
+
+    contents
+        .lines()
+        .filter_map(|line| {
+            let (part, answer) = line.split_once('\t')?;
+            let part = Part::from_str(part, true).ok()?;
+            Some((part, answer.replace("\\n", "\n")))
+        })
+        .collect()
+}
+
+fn save_answers(path: &Path, answers: &Answers) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let contents = answers
+        .iter()
+        .map(|(part, answer)| format!("{}\t{}\n", part, answer.replace('\n', "\\n")))
+        .collect::<String>();
+
+    fs::write(path, contents)
+}
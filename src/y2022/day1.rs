@@ -0,0 +1,773 @@
+use std::{cmp::Reverse, collections::BinaryHeap, io::BufRead};
+
+use log::debug;
+
+use crate::{
+    cancel,
+    parse::{self, ParseError, ParseResult},
+    search::{BranchAndBound, Incumbent, Problem},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Elf {
+    /// Position of the elf in the input, from 0
+    pub index: usize,
+    pub items: Vec<u64>,
+    pub total: u64,
+}
+
+fn parse_elf(index: usize, block: &str) -> ParseResult<Elf> {
+    let items = parse::parse_lines(block, parse::number)?;
+    let mut total = 0u64;
+
+    for (i, item) in items.iter().enumerate() {
+        total = total
+            .checked_add(*item)
+            .ok_or_else(|| ParseError::new("the elf's calories overflow").at_line(i + 1))?;
+    }
+
+    Ok(Elf {
+        index,
+        items,
+        total,
+    })
+}
+
+pub fn parse_input(input: &str) -> ParseResult<Vec<Elf>> {
+    let mut index = 0;
+
+    parse::parse_blocks(input, |block| {
+        index += 1;
+        parse_elf(index - 1, block)
+    })
+}
+
+/// Entry point for fuzzing the parser.
+pub fn fuzz(input: &str) -> ParseResult<()> {
+    parse_input(input).map(drop)
+}
+
+/// The elves carrying the most calories, best first. Of elves with equal totals the earlier
+/// one ranks higher.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ranking {
+    /// `(index, total)` of each elf in the top
+    pub top: Vec<(usize, u64)>,
+    /// The first few elves left out of the top that carry as much as the last one in it
+    pub tied: Vec<usize>,
+    /// How many elves are tied in all, including those not in `tied`
+    pub tied_count: usize,
+}
+
+impl Ranking {
+    /// The calories carried by the whole top, `None` if they overflow.
+    pub fn total(&self) -> Option<u64> {
+        self.top
+            .iter()
+            .try_fold(0u64, |sum, (_, total)| sum.checked_add(*total))
+    }
+}
+
+/// How many of the elves tied at the cutoff a [`Ranking`] names.
+const TIED_SAMPLE: usize = 10;
+
+/// Keeps the top k elves in a heap of k elves as they come in, so any number of elves can be
+/// ranked without holding on to them.
+#[derive(Debug, Clone)]
+pub struct TopK {
+    k: usize,
+    /// Min-heap on the total, so the elf to drop is on top. Of equal totals the later elf goes
+    heap: BinaryHeap<Reverse<(u64, Reverse<usize>)>>,
+    /// The lowest indices of the tied elves, sorted and at most [`TIED_SAMPLE`] of them
+    tied: Vec<usize>,
+    tied_count: usize,
+    /// Total of the tied elves
+    tied_total: u64,
+}
+
+impl TopK {
+    pub fn new(k: usize) -> Self {
+        Self {
+            k,
+            heap: BinaryHeap::with_capacity(k + 1),
+            tied: Vec::new(),
+            tied_count: 0,
+            tied_total: 0,
+        }
+    }
+
+    pub fn push(&mut self, index: usize, total: u64) {
+        if self.k == 0 {
+            return;
+        }
+
+        self.heap.push(Reverse((total, Reverse(index))));
+
+        if self.heap.len() <= self.k {
+            return;
+        }
+
+        let Some(Reverse((dropped, Reverse(dropped_index)))) = self.heap.pop() else {
+            return;
+        };
+        let cutoff = self.heap.peek().map_or(0, |Reverse((total, _))| *total);
+
+        if self.tied_total != cutoff {
+            self.tied.clear();
+            self.tied_count = 0;
+            self.tied_total = cutoff;
+        }
+
+        if dropped == cutoff {
+            let position = self.tied.partition_point(|&index| index < dropped_index);
+            self.tied.insert(position, dropped_index);
+            self.tied.truncate(TIED_SAMPLE);
+            self.tied_count += 1;
+        }
+    }
+
+    pub fn finish(self) -> Ranking {
+        let mut top = self
+            .heap
+            .into_iter()
+            .map(|Reverse((total, Reverse(index)))| (index, total))
+            .collect::<Vec<_>>();
+        top.sort_by_key(|&(index, total)| (Reverse(total), index));
+
+        Ranking {
+            top,
+            tied: self.tied,
+            tied_count: self.tied_count,
+        }
+    }
+}
+
+pub fn top_k(elves: &[Elf], k: usize) -> Ranking {
+    let mut top = TopK::new(k);
+
+    for elf in elves {
+        top.push(elf.index, elf.total);
+    }
+
+    top.finish()
+}
+
+/// Ranks the elves of a calorie log of any size in a single pass, holding one line and the top
+/// k elves at a time. Reads the same format as the puzzle input.
+pub fn top_k_streaming(mut reader: impl BufRead, k: usize) -> ParseResult<Ranking> {
+    let mut top = TopK::new(k);
+    let mut line = String::new();
+    let mut line_number = 0;
+    let mut index = 0;
+    let mut total = 0u64;
+    let mut after_separator = false;
+
+    loop {
+        line.clear();
+        line_number += 1;
+
+        let read = reader
+            .read_line(&mut line)
+            .map_err(|error| ParseError::new(error.to_string()).at_line(line_number))?;
+
+        if read == 0 {
+            break;
+        }
+
+        let item = line.trim_end_matches('\n');
+        let item = item.strip_suffix('\r').unwrap_or(item);
+
+        // A blank line ends the elf, one right after it is an empty item like in `parse_input`
+        if item.is_empty() && !after_separator {
+            top.push(index, total);
+            index += 1;
+            total = 0;
+            after_separator = true;
+            continue;
+        }
+
+        after_separator = false;
+        total = parse::number::<u64>(item)
+            .and_then(|item| {
+                total
+                    .checked_add(item)
+                    .ok_or_else(|| ParseError::new("the elf's calories overflow"))
+            })
+            .map_err(|error| error.at_line(line_number))?;
+    }
+
+    top.push(index, total);
+
+    Ok(top.finish())
+}
+
+fn top_total(input: &str, k: usize) -> ParseResult<String> {
+    let ranking = top_k_streaming(input.as_bytes(), k)?;
+    let total = ranking
+        .total()
+        .ok_or_else(|| ParseError::new("the total calories overflow"))?;
+
+    Ok(total.to_string())
+}
+
+/// Number of bars in the histogram of the totals.
+const HISTOGRAM_BINS: u64 = 10;
+/// Length of the longest bar.
+const HISTOGRAM_WIDTH: usize = 40;
+
+/// Elves whose totals fall in `start..=end`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bin {
+    pub start: u64,
+    pub end: u64,
+    pub count: usize,
+}
+
+/// The distribution of the elves' totals and items.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    pub elves: usize,
+    pub mean: f64,
+    pub median: f64,
+    /// `(percent, total)`, interpolating between the elves on either side
+    pub percentiles: Vec<(u8, f64)>,
+    pub min: u64,
+    pub max: u64,
+    /// `(elf index, calories)` of the largest single item, if there are any items
+    pub largest_item: Option<(usize, u64)>,
+    pub items_per_elf: f64,
+    pub most_items: usize,
+    /// Equal width ranges of totals from `min` to `max`, empty ones included
+    pub histogram: Vec<Bin>,
+}
+
+impl Stats {
+    /// `None` if there are no elves.
+    pub fn of(elves: &[Elf]) -> Option<Self> {
+        let mut totals = elves.iter().map(|elf| elf.total).collect::<Vec<_>>();
+        totals.sort_unstable();
+
+        let (&min, &max) = (totals.first()?, totals.last()?);
+        let sum = totals.iter().map(|&total| total as u128).sum::<u128>();
+        let items = elves.iter().map(|elf| elf.items.len()).sum::<usize>();
+
+        let largest_item = elves
+            .iter()
+            .flat_map(|elf| elf.items.iter().map(|&item| (elf.index, item)))
+            .max_by_key(|&(index, item)| (item, Reverse(index)));
+
+        Some(Self {
+            elves: elves.len(),
+            mean: sum as f64 / elves.len() as f64,
+            median: percentile(&totals, 50),
+            percentiles: [10, 25, 75, 90, 99]
+                .into_iter()
+                .map(|percent| (percent, percentile(&totals, percent)))
+                .collect(),
+            min,
+            max,
+            largest_item,
+            items_per_elf: items as f64 / elves.len() as f64,
+            most_items: elves.iter().map(|elf| elf.items.len()).max().unwrap_or(0),
+            histogram: histogram(&totals),
+        })
+    }
+
+    pub fn render_text(&self) -> String {
+        let mut lines = vec![
+            format!("{:<16}{}", "elves", self.elves),
+            format!("{:<16}{:.1}", "mean", self.mean),
+            format!("{:<16}{:.1}", "median", self.median),
+        ];
+
+        for (percent, total) in &self.percentiles {
+            lines.push(format!("{:<16}{:.1}", format!("p{}", percent), total));
+        }
+
+        lines.push(format!("{:<16}{}", "min", self.min));
+        lines.push(format!("{:<16}{}", "max", self.max));
+
+        if let Some((index, item)) = self.largest_item {
+            lines.push(format!("{:<16}{} (elf {})", "largest item", item, index));
+        }
+
+        lines.push(format!("{:<16}{:.1}", "items per elf", self.items_per_elf));
+        lines.push(format!("{:<16}{}", "most items", self.most_items));
+
+        let largest = self
+            .histogram
+            .iter()
+            .map(|bin| bin.count)
+            .max()
+            .unwrap_or(0);
+        let label_width = self.max.to_string().len();
+        lines.push(String::new());
+
+        for bin in &self.histogram {
+            let bar = (bin.count * HISTOGRAM_WIDTH).div_ceil(largest.max(1));
+            lines.push(format!(
+                "{:>w$}-{:>w$} {:<bar_width$} {}",
+                bin.start,
+                bin.end,
+                "#".repeat(bar),
+                bin.count,
+                w = label_width,
+                bar_width = HISTOGRAM_WIDTH
+            ));
+        }
+
+        lines.join("\n")
+    }
+
+    pub fn render_json(&self) -> String {
+        let percentiles = self
+            .percentiles
+            .iter()
+            .map(|(percent, total)| format!("\"p{}\":{}", percent, total))
+            .collect::<Vec<_>>();
+        let largest_item = match self.largest_item {
+            Some((index, item)) => format!("{{\"elf\":{},\"calories\":{}}}", index, item),
+            None => "null".to_string(),
+        };
+        let histogram = self
+            .histogram
+            .iter()
+            .map(|bin| {
+                format!(
+                    "{{\"start\":{},\"end\":{},\"count\":{}}}",
+                    bin.start, bin.end, bin.count
+                )
+            })
+            .collect::<Vec<_>>();
+
+        format!(
+            "{{\"elves\":{},\"mean\":{},\"median\":{},\"percentiles\":{{{}}},\"min\":{},\"max\":{},\
+             \"largest_item\":{},\"items_per_elf\":{},\"most_items\":{},\"histogram\":[{}]}}",
+            self.elves,
+            self.mean,
+            self.median,
+            percentiles.join(","),
+            self.min,
+            self.max,
+            largest_item,
+            self.items_per_elf,
+            self.most_items,
+            histogram.join(",")
+        )
+    }
+}
+
+/// The `percent`th percentile of the sorted, non-empty `values`.
+fn percentile(values: &[u64], percent: u8) -> f64 {
+    let rank = (values.len() - 1) as f64 * percent as f64 / 100.0;
+    let (low, high) = (values[rank.floor() as usize], values[rank.ceil() as usize]);
+
+    low as f64 + (high as f64 - low as f64) * rank.fract()
+}
+
+fn histogram(sorted: &[u64]) -> Vec<Bin> {
+    let (min, max) = (sorted[0], sorted[sorted.len() - 1]);
+    let width = (max - min) / HISTOGRAM_BINS + 1;
+
+    let mut bins = (0..=(max - min) / width)
+        .map(|i| Bin {
+            start: min + i * width,
+            end: (min + i * width).saturating_add(width - 1).min(max),
+            count: 0,
+        })
+        .collect::<Vec<_>>();
+
+    for total in sorted {
+        bins[((total - min) / width) as usize].count += 1;
+    }
+
+    bins
+}
+
+/// Items split into groups so that the heaviest group is as light as possible.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Partition {
+    pub groups: Vec<Vec<u64>>,
+    /// Total of the heaviest group
+    pub largest: u64,
+    /// No split can have a lighter heaviest group: the even share or the largest item
+    pub lower_bound: u64,
+    /// Whether `largest` is proven to be the best possible
+    pub optimal: bool,
+}
+
+impl Partition {
+    /// How much heavier the heaviest group may be than in the best split, relative to the
+    /// lower bound.
+    pub fn gap(&self) -> f64 {
+        if self.optimal || self.lower_bound == 0 {
+            return 0.0;
+        }
+
+        (self.largest - self.lower_bound) as f64 / self.lower_bound as f64
+    }
+}
+
+/// Up to this many items are split exactly, more with the greedy heuristic only.
+pub const EXACT_PARTITION_LIMIT: usize = 20;
+
+/// Splits the items into k groups, exactly if there are few enough of them. `None` if k is 0
+/// or the items' total overflows.
+pub fn partition(items: &[u64], k: usize) -> Option<Partition> {
+    if items.len() <= EXACT_PARTITION_LIMIT {
+        partition_exact(items, k)
+    } else {
+        partition_greedy(items, k)
+    }
+}
+
+/// Puts the items, heaviest first, into the lightest group so far. The heaviest group is at
+/// most 4/3 of the best possible.
+pub fn partition_greedy(items: &[u64], k: usize) -> Option<Partition> {
+    if k == 0 {
+        return None;
+    }
+
+    let total = items
+        .iter()
+        .try_fold(0u64, |sum, &item| sum.checked_add(item))?;
+    let largest_item = items.iter().max().copied().unwrap_or(0);
+    let lower_bound = total.div_ceil(k as u64).max(largest_item);
+
+    let mut sorted = items.to_vec();
+    sorted.sort_unstable_by_key(|&item| Reverse(item));
+
+    let mut groups = vec![Vec::new(); k.min(items.len().max(1))];
+    let mut lightest = (0..groups.len())
+        .map(|group| Reverse((0u64, group)))
+        .collect::<BinaryHeap<_>>();
+
+    for item in sorted {
+        let Some(Reverse((sum, group))) = lightest.pop() else {
+            break;
+        };
+        groups[group].push(item);
+        lightest.push(Reverse((sum + item, group)));
+    }
+
+    let largest = lightest
+        .into_iter()
+        .map(|Reverse((sum, _))| sum)
+        .max()
+        .unwrap_or(0);
+    groups.resize(k, Vec::new());
+
+    Some(Partition {
+        groups,
+        largest,
+        lower_bound,
+        optimal: largest == lower_bound,
+    })
+}
+
+/// The items heaviest first and what is left to place after each of them.
+struct Packing {
+    items: Vec<u64>,
+    remaining: Vec<u64>,
+}
+
+/// The totals of the groups, in ascending order as groups with equal totals are
+/// interchangeable, after placing the first `next` items.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+struct Loads {
+    next: usize,
+    sums: Vec<u64>,
+}
+
+/// Scores are `u64::MAX` minus the heaviest group, as the search maximizes.
+impl Problem for Packing {
+    type State = Loads;
+    type Score = u64;
+
+    fn leaf_score(&self, state: &Loads) -> Option<u64> {
+        (state.next == self.items.len()).then(|| u64::MAX - state.sums.last().unwrap_or(&0))
+    }
+
+    fn branch(&self, state: &Loads, out: &mut Vec<Loads>) {
+        let item = self.items[state.next];
+
+        for (i, &sum) in state.sums.iter().enumerate() {
+            if i > 0 && state.sums[i - 1] == sum {
+                continue;
+            }
+
+            let mut sums = state.sums.clone();
+            sums[i] += item;
+            sums.sort_unstable();
+            out.push(Loads {
+                next: state.next + 1,
+                sums,
+            });
+        }
+    }
+
+    fn upper_bound(&self, state: &Loads) -> Option<u64> {
+        let total = state.sums.iter().sum::<u64>() + self.remaining[state.next];
+        let even_share = total.div_ceil(state.sums.len() as u64);
+
+        Some(u64::MAX - even_share.max(*state.sums.last()?))
+    }
+}
+
+/// Puts the items, heaviest first, into groups of at most `capacity`.
+fn fit(items: &[u64], capacity: u64, groups: &mut [(u64, Vec<u64>)]) -> bool {
+    let Some((&item, rest)) = items.split_first() else {
+        return true;
+    };
+
+    for i in 0..groups.len() {
+        let sum = groups[i].0;
+
+        if sum + item > capacity || groups[..i].iter().any(|(other, _)| *other == sum) {
+            continue;
+        }
+
+        groups[i].0 += item;
+        groups[i].1.push(item);
+
+        if fit(rest, capacity, groups) {
+            return true;
+        }
+
+        groups[i].0 -= item;
+        groups[i].1.pop();
+    }
+
+    false
+}
+
+/// Branch-and-bound over the group of every item, starting from the greedy split. Takes
+/// exponential time, so only for around twenty items. Falls back on the greedy split if
+/// cancelled.
+pub fn partition_exact(items: &[u64], k: usize) -> Option<Partition> {
+    let greedy = partition_greedy(items, k)?;
+
+    if greedy.optimal {
+        return Some(greedy);
+    }
+
+    let mut sorted = items.to_vec();
+    sorted.sort_unstable_by_key(|&item| Reverse(item));
+
+    let mut remaining = vec![0; sorted.len() + 1];
+    for i in (0..sorted.len()).rev() {
+        remaining[i] = remaining[i + 1] + sorted[i];
+    }
+
+    let groups = k.min(sorted.len());
+    let problem = Packing {
+        items: sorted,
+        remaining,
+    };
+    let token = cancel::current();
+    // Only splits better than the greedy one are worth finding
+    let incumbent = Incumbent::new(u64::MAX - greedy.largest + 1);
+    let search = BranchAndBound::new(&problem, &incumbent)
+        .memoize()
+        .cancellable(token.clone());
+    let score = search.solve(Loads {
+        next: 0,
+        sums: vec![0; groups],
+    });
+    debug!("Branch-and-bound: {}", search.stats());
+
+    if token.is_cancelled() {
+        return Some(greedy);
+    }
+
+    let largest = score.map_or(greedy.largest, |score| u64::MAX - score);
+
+    if largest >= greedy.largest {
+        return Some(Partition {
+            optimal: true,
+            ..greedy
+        });
+    }
+
+    let mut fitted = vec![(0, Vec::new()); groups];
+    fit(&problem.items, largest, &mut fitted);
+
+    let mut groups = fitted
+        .into_iter()
+        .map(|(_, group)| group)
+        .collect::<Vec<_>>();
+    groups.resize(k, Vec::new());
+
+    Some(Partition {
+        groups,
+        largest,
+        lower_bound: greedy.lower_bound,
+        optimal: true,
+    })
+}
+
+pub fn explain(input: &str) -> ParseResult<String> {
+    let elves = parse_input(input)?;
+    let ranking = top_k(&elves, 3);
+    let mut lines = ranking
+        .top
+        .iter()
+        .enumerate()
+        .map(|(rank, (index, total))| format!("{}. elf {}: {}", rank + 1, index, total))
+        .collect::<Vec<_>>();
+
+    if !ranking.tied.is_empty() {
+        let mut tied = ranking
+            .tied
+            .iter()
+            .map(|index| index.to_string())
+            .collect::<Vec<_>>();
+
+        if ranking.tied_count > ranking.tied.len() {
+            tied.push(format!("{} more", ranking.tied_count - ranking.tied.len()));
+        }
+
+        lines.push(format!("tied: elf {}", tied.join(", ")));
+    }
+
+    if let Some(stats) = Stats::of(&elves) {
+        lines.push(String::new());
+        lines.push(stats.render_text());
+    }
+
+    Ok(lines.join("\n"))
+}
+
+pub fn day1a(input: &str) -> ParseResult<String> {
+    top_total(input, 1)
+}
+
+pub fn day1b(input: &str) -> ParseResult<String> {
+    top_total(input, 3)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapshot::assert_snapshot;
+
+    const EXAMPLE: &str = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n";
+
+    #[test]
+    fn ranks_the_example() {
+        let elves = parse_input(EXAMPLE).unwrap();
+        assert_eq!(elves.len(), 5);
+        assert_eq!(elves[2].items, vec![5000, 6000]);
+
+        let ranking = top_k(&elves, 3);
+        assert_eq!(ranking.top, vec![(3, 24000), (2, 11000), (4, 10000)]);
+        assert_eq!(ranking.total(), Some(45000));
+        assert!(ranking.tied.is_empty());
+
+        assert_eq!(day1a(EXAMPLE).unwrap(), "24000");
+        assert_eq!(top_k(&elves, 10).top.len(), 5);
+        assert_eq!(top_k(&elves, 0).top, vec![]);
+    }
+
+    #[test]
+    fn reports_ties_at_the_cutoff() {
+        let elves = parse_input("5\n\n3\n\n7\n\n3\n\n1\n\n3").unwrap();
+
+        let ranking = top_k(&elves, 2);
+        assert_eq!(ranking.top, vec![(2, 7), (0, 5)]);
+        assert!(ranking.tied.is_empty());
+
+        let ranking = top_k(&elves, 3);
+        assert_eq!(ranking.top, vec![(2, 7), (0, 5), (1, 3)]);
+        assert_eq!(ranking.tied, vec![3, 5]);
+        assert_eq!(ranking.tied_count, 2);
+
+        let input = vec!["2"; 100].join("\n\n");
+        let ranking = top_k(&parse_input(&input).unwrap(), 3);
+        assert_eq!(ranking.tied, (3..3 + TIED_SAMPLE).collect::<Vec<_>>());
+        assert_eq!(ranking.tied_count, 97);
+    }
+
+    #[test]
+    fn detects_overflow() {
+        let max = u64::MAX;
+        let error = parse_input(&format!("1\n\n{}\n1\n", max)).unwrap_err();
+        assert_eq!(error.line, Some(4));
+
+        let input = format!("{}\n\n{}\n", max, max);
+        assert!(parse_input(&input).is_ok());
+        assert!(day1b(&input).is_err());
+    }
+
+    #[test]
+    fn describes_the_distribution() {
+        let stats = Stats::of(&parse_input(EXAMPLE).unwrap()).unwrap();
+
+        assert_eq!(stats.elves, 5);
+        assert_eq!(stats.mean, 11000.0);
+        assert_eq!(stats.median, 10000.0);
+        assert_eq!(stats.percentiles[1], (25, 6000.0));
+        assert_eq!(stats.percentiles[0], (10, 4800.0));
+        assert_eq!(stats.largest_item, Some((4, 10000)));
+        assert_eq!(stats.items_per_elf, 2.0);
+        assert_eq!(stats.most_items, 3);
+        assert_eq!(
+            stats.histogram.iter().map(|bin| bin.count).sum::<usize>(),
+            5
+        );
+        assert_eq!(stats.histogram.last().unwrap().end, 24000);
+
+        assert_snapshot("day1_stats", &stats.render_text());
+        assert!(stats.render_json().starts_with(
+            "{\"elves\":5,\"mean\":11000,\"median\":10000,\"percentiles\":{\"p10\":4800,"
+        ));
+
+        assert_eq!(Stats::of(&[]), None);
+    }
+
+    #[test]
+    fn partitions_items_evenly() {
+        let items = parse_input(EXAMPLE)
+            .unwrap()
+            .into_iter()
+            .flat_map(|elf| elf.items)
+            .collect::<Vec<_>>();
+
+        let split = partition(&items, 3).unwrap();
+        assert_eq!((split.largest, split.lower_bound), (19000, 18334));
+        assert!(split.optimal);
+        assert_eq!(split.groups.concat().iter().sum::<u64>(), 55000);
+
+        let greedy = partition_greedy(&[3, 3, 2, 2, 2], 2).unwrap();
+        assert_eq!((greedy.largest, greedy.optimal), (7, false));
+        assert!((greedy.gap() - 1.0 / 6.0).abs() < 1e-9);
+
+        let exact = partition_exact(&[3, 3, 2, 2, 2], 2).unwrap();
+        assert_eq!((exact.largest, exact.optimal, exact.gap()), (6, true, 0.0));
+        for group in &exact.groups {
+            assert_eq!(group.iter().sum::<u64>(), 6);
+        }
+
+        assert_eq!(
+            partition(&[5, 1], 4).unwrap().groups,
+            vec![vec![5], vec![1], vec![], vec![]]
+        );
+        assert_eq!(partition(&[1], 0), None);
+        assert_eq!(partition(&[u64::MAX, 1], 2), None);
+    }
+
+    #[test]
+    fn streaming_matches_parsing() {
+        for input in [EXAMPLE, "", "5\n\n3\n\n7\n\n3\n\n1\n\n3", "4\n\n"] {
+            for k in [1, 3] {
+                let parsed = top_k(&parse_input(input).unwrap(), k);
+                assert_eq!(top_k_streaming(input.as_bytes(), k).unwrap(), parsed);
+            }
+        }
+
+        for input in ["1\n\n\n2\n", "1\n2\nx\n", "1\n\n2\n3\n\n-4\n"] {
+            let parsed = parse_input(input).unwrap_err();
+            assert_eq!(top_k_streaming(input.as_bytes(), 1).unwrap_err(), parsed);
+        }
+    }
+}
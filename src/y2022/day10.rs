@@ -0,0 +1,210 @@
+use crate::{
+    debugger::Simulation,
+    parse::{self, ParseError, ParseResult},
+};
+
+#[derive(Debug, Clone, Copy)]
+enum Instruction {
+    Noop,
+    Addx(i64),
+}
+
+impl Instruction {
+    fn cycles(self) -> u8 {
+        match self {
+            Instruction::Noop => 1,
+            Instruction::Addx(_) => 2,
+        }
+    }
+}
+
+fn parse_instruction(s: &str) -> ParseResult<Instruction> {
+    match s.split_whitespace().collect::<Vec<_>>()[..] {
+        ["noop"] => Ok(Instruction::Noop),
+        ["addx", value] => Ok(Instruction::Addx(parse::number(value)?)),
+        _ => Err(ParseError::new(format!("unknown instruction `{}`", s))),
+    }
+}
+
+fn parse_input(input: &str) -> ParseResult<Vec<Instruction>> {
+    let instructions = parse::parse_lines(input, parse_instruction)?;
+
+    if instructions.is_empty() {
+        return Err(ParseError::new("empty program"));
+    }
+
+    Ok(instructions)
+}
+
+/// Entry point for fuzzing the parser.
+pub fn fuzz(input: &str) -> ParseResult<()> {
+    parse_input(input).map(drop)
+}
+
+#[derive(Clone)]
+struct Vm {
+    x: i64,
+    cycles: i64,
+    halted: bool,
+    instructions: Vec<Instruction>,
+    instruction_pointer: usize,
+    cycles_to_next_instruction: u8,
+}
+
+impl Vm {
+    fn new(instructions: Vec<Instruction>) -> Self {
+        Self {
+            x: 1,
+            cycles: 1,
+            halted: false,
+            cycles_to_next_instruction: instructions[0].cycles(),
+            instructions,
+            instruction_pointer: 0,
+        }
+    }
+
+    fn get_current_instruction(&self) -> Option<Instruction> {
+        self.instructions.get(self.instruction_pointer).copied()
+    }
+
+    fn execute_cycle(&mut self) {
+        if self.halted {
+            return;
+        }
+
+        if self.cycles_to_next_instruction > 0 {
+            self.cycles += 1;
+            self.cycles_to_next_instruction -= 1;
+        }
+
+        if self.cycles_to_next_instruction == 0 {
+            let current_instruction = self.get_current_instruction();
+            self.execute_instruction(current_instruction.unwrap());
+            self.instruction_pointer += 1;
+
+            if let Some(instruction) = self.get_current_instruction() {
+                self.cycles_to_next_instruction = instruction.cycles();
+            } else {
+                self.halted = true;
+            }
+        }
+    }
+
+    fn execute_instruction(&mut self, instruction: Instruction) {
+        match instruction {
+            Instruction::Noop => {}
+            Instruction::Addx(x) => {
+                self.x += x;
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct VmSimulation {
+    vm: Vm,
+    signal_sum: i64,
+}
+
+impl VmSimulation {
+    pub fn new(input: &str) -> ParseResult<Self> {
+        Ok(Self {
+            vm: Vm::new(parse_input(input)?),
+            signal_sum: 0,
+        })
+    }
+}
+
+impl Simulation for VmSimulation {
+    fn step(&mut self) -> bool {
+        if self.vm.halted {
+            return false;
+        }
+
+        self.vm.execute_cycle();
+
+        if (self.vm.cycles + 20) % 40 == 0 {
+            self.signal_sum += self.vm.cycles * self.vm.x;
+        }
+
+        true
+    }
+
+    fn render(&self) -> String {
+        let vm = &self.vm;
+
+        let instruction = match vm.get_current_instruction() {
+            Some(instruction) => format!(
+                "{:?} ({} cycles left)",
+                instruction, vm.cycles_to_next_instruction
+            ),
+            None => "halted".to_string(),
+        };
+
+        format!(
+            "Cycle: {}\nX: {}\nIP: {}/{}\nInstruction: {}\nSignal strength sum: {}",
+            vm.cycles,
+            vm.x,
+            vm.instruction_pointer,
+            vm.instructions.len(),
+            instruction,
+            self.signal_sum
+        )
+    }
+}
+
+pub fn day10a(input: &str) -> ParseResult<String> {
+    let instructions = parse_input(input)?;
+    let mut vm = Vm::new(instructions);
+
+    let mut signal_sum = 0;
+
+    while !vm.halted {
+        vm.execute_cycle();
+
+        if (vm.cycles + 20) % 40 == 0 {
+            let signal_strength = vm.cycles * vm.x;
+            signal_sum += signal_strength;
+        }
+    }
+
+    Ok(signal_sum.to_string())
+}
+
+pub fn day10b(input: &str) -> ParseResult<String> {
+    let instructions = parse_input(input)?;
+    let mut vm = Vm::new(instructions);
+
+    let mut screen_buffer: [char; 40 * 6] = ['.'; 40 * 6];
+
+    for i in 0..screen_buffer.len() {
+        let x_pos = i % 40;
+        if (vm.x - x_pos as i64).abs() <= 1 {
+            screen_buffer[i] = '#';
+        } else {
+            screen_buffer[i] = '.';
+        }
+
+        vm.execute_cycle();
+    }
+
+    Ok(screen_buffer
+        .chunks(40)
+        .map(|line| line.iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapshot::assert_snapshot;
+
+    #[test]
+    fn renders_screen() {
+        assert_snapshot(
+            "day10b_screen",
+            &day10b(include_str!("day10.input")).unwrap(),
+        );
+    }
+}
@@ -0,0 +1,424 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+use log::info;
+
+use crate::{
+    cancel,
+    debugger::Simulation,
+    parse::{self, ParseError, ParseResult},
+};
+
+#[derive(Debug, Clone)]
+enum OperationValue {
+    Old,
+    Int(i64),
+}
+
+#[derive(Debug, Clone)]
+enum Operator {
+    Mul,
+    Add,
+}
+
+type Expression = (OperationValue, Operator, OperationValue);
+
+fn eval_expression(expr: &Expression, old: i64) -> i64 {
+    let (l, op, r) = expr;
+    let l = match l {
+        OperationValue::Old => old,
+        OperationValue::Int(i) => *i,
+    };
+    let r = match r {
+        OperationValue::Old => old,
+        OperationValue::Int(i) => *i,
+    };
+    match op {
+        Operator::Mul => l * r,
+        Operator::Add => l + r,
+    }
+}
+
+fn parse_op_value(op: &str) -> ParseResult<OperationValue> {
+    if op == "old" {
+        Ok(OperationValue::Old)
+    } else {
+        Ok(OperationValue::Int(parse::number(op)?))
+    }
+}
+
+fn parse_operation(expr: &str) -> ParseResult<Expression> {
+    let expr = expr
+        .strip_prefix("new = ")
+        .ok_or_else(|| ParseError::new(format!("invalid operation `{}`", expr)))?;
+
+    match expr.split(' ').collect_tuple() {
+        Some((l, "+", r)) => Ok((parse_op_value(l)?, Operator::Add, parse_op_value(r)?)),
+        Some((l, "*", r)) => Ok((parse_op_value(l)?, Operator::Mul, parse_op_value(r)?)),
+        _ => Err(ParseError::new(format!("invalid operation `{}`", expr))),
+    }
+}
+
+fn parse_starting_items(items: &str) -> ParseResult<Vec<i64>> {
+    if items.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    items.split(", ").map(parse::number).collect()
+}
+
+#[derive(Debug, Clone)]
+struct Monkey {
+    items: Vec<i64>,
+    operation: Expression,
+    divisible: i64,
+    divisible_if_true: usize,
+    divisible_if_false: usize,
+}
+
+fn parse_monkey(monkey_lines: &str) -> ParseResult<Monkey> {
+    let monkey_lines = monkey_lines.lines().collect_vec();
+
+    // Returns the text after `label` on the i:th line
+    let field = |i: usize, label: &str| {
+        monkey_lines
+            .get(i)
+            .and_then(|line| line.trim().strip_prefix(label))
+            .map(str::trim)
+            .ok_or_else(|| ParseError::new(format!("expected `{}`", label)).at_line(i + 1))
+    };
+
+    let at_line = |i: usize| move |error: ParseError| error.at_line(i + 1);
+
+    // skip monkey ID
+    field(0, "Monkey")?;
+    let starting_items = parse_starting_items(field(1, "Starting items:")?).map_err(at_line(1))?;
+    let operation = parse_operation(field(2, "Operation:")?).map_err(at_line(2))?;
+    let divisible: i64 = parse::number(field(3, "Test: divisible by")?).map_err(at_line(3))?;
+    let divisible_if_true =
+        parse::number(field(4, "If true: throw to monkey")?).map_err(at_line(4))?;
+    let divisible_if_false =
+        parse::number(field(5, "If false: throw to monkey")?).map_err(at_line(5))?;
+
+    if divisible <= 0 {
+        return Err(ParseError::new("divisor must be positive").at_line(4));
+    }
+
+    if monkey_lines.len() > 6 {
+        return Err(ParseError::new("unexpected line after monkey").at_line(7));
+    }
+
+    Ok(Monkey {
+        items: starting_items,
+        operation,
+        divisible,
+        divisible_if_true,
+        divisible_if_false,
+    })
+}
+
+fn parse_input(input: &str) -> ParseResult<Vec<Monkey>> {
+    let monkeys = parse::parse_blocks(input, parse_monkey)?;
+
+    for (i, monkey) in monkeys.iter().enumerate() {
+        for target in [monkey.divisible_if_true, monkey.divisible_if_false] {
+            if target >= monkeys.len() {
+                return Err(ParseError::new(format!(
+                    "monkey {} throws to missing monkey {}",
+                    i, target
+                )));
+            }
+        }
+    }
+
+    monkeys
+        .iter()
+        .try_fold(1_i64, |product, monkey| {
+            product.checked_mul(monkey.divisible)
+        })
+        .ok_or_else(|| ParseError::new("product of the divisors is too large"))?;
+
+    Ok(monkeys)
+}
+
+/// Entry point for fuzzing the parser.
+pub fn fuzz(input: &str) -> ParseResult<()> {
+    parse_input(input).map(drop)
+}
+
+impl Monkey {
+    fn process_turn(
+        &mut self,
+        outbound_items: &mut HashMap<usize, Vec<i64>>,
+        divide: bool,
+        mod_by: i64,
+    ) -> usize {
+        if self.items.is_empty() {
+            return 0;
+        }
+
+        let inspected_items = self.items.len();
+
+        for item in self.items.iter().copied() {
+            let mut item = eval_expression(&self.operation, item);
+
+            if divide {
+                item /= 3;
+            }
+
+            item %= mod_by;
+
+            if item % self.divisible == 0 {
+                outbound_items
+                    .entry(self.divisible_if_true)
+                    .or_default()
+                    .push(item);
+            } else {
+                outbound_items
+                    .entry(self.divisible_if_false)
+                    .or_default()
+                    .push(item);
+            }
+        }
+
+        self.items.clear();
+
+        inspected_items
+    }
+}
+
+fn process_round(
+    monkeys: &mut [Monkey],
+    inspections: &mut HashMap<usize, usize>,
+    divide: bool,
+    mod_by: i64,
+) -> HashMap<usize, Vec<i64>> {
+    let mut outbound_items = HashMap::new();
+
+    for i in 0..monkeys.len() {
+        let monkey = &mut monkeys[i];
+        let inspected_items = monkey.process_turn(&mut outbound_items, divide, mod_by);
+
+        *inspections.entry(i).or_default() += inspected_items;
+
+        for (monkey, items) in outbound_items.drain() {
+            monkeys[monkey].items.extend(items);
+        }
+    }
+
+    outbound_items
+}
+
+fn common_modulus(monkeys: &[Monkey]) -> i64 {
+    monkeys.iter().map(|monkey| monkey.divisible).product()
+}
+
+/// How many items each monkey inspected over the rounds.
+/// The inspections of every monkey, and how many of the rounds were played before the run was
+/// cancelled.
+fn count_inspections(mut monkeys: Vec<Monkey>, rounds: usize, divide: bool) -> (Vec<usize>, usize) {
+    let mod_by = common_modulus(&monkeys);
+
+    let token = cancel::current();
+    let mut inspections = HashMap::new();
+    let mut played = 0;
+
+    while played < rounds && !token.is_cancelled() {
+        process_round(&mut monkeys, &mut inspections, divide, mod_by);
+        played += 1;
+    }
+
+    let inspections = (0..monkeys.len())
+        .map(|i| inspections.get(&i).copied().unwrap_or(0))
+        .collect();
+
+    (inspections, played)
+}
+
+fn monkey_business(inspections: &[usize]) -> usize {
+    inspections
+        .iter()
+        .copied()
+        .sorted()
+        .rev()
+        .take(2)
+        .product::<usize>()
+}
+
+/// The monkey business after the rounds. If cancelled, the business so far is not a bound on
+/// the final one, so it's labelled with the rounds it covers.
+fn process_rounds(monkeys: Vec<Monkey>, rounds: usize, divide: bool) -> String {
+    let (inspections, played) = count_inspections(monkeys, rounds, divide);
+    let business = monkey_business(&inspections);
+
+    if played < rounds {
+        info!("Stopped after {} of {} rounds", played, rounds);
+        return format!("{} after {} of {} rounds", business, played, rounds);
+    }
+
+    business.to_string()
+}
+
+/// The inspections of every monkey under the rules of both parts.
+pub fn explain(input: &str) -> ParseResult<String> {
+    let monkeys = parse_input(input)?;
+    let (a, _) = count_inspections(monkeys.clone(), 20, true);
+    let (b, _) = count_inspections(monkeys, 10_000, false);
+
+    let mut lines = vec![format!(
+        "{:<10}{:>12}{:>14}",
+        "", "20 rounds", "10000 rounds"
+    )];
+
+    for (i, (a, b)) in a.iter().zip(&b).enumerate() {
+        lines.push(format!("Monkey {:<3}{:>12}{:>14}", i, a, b));
+    }
+
+    lines.push(format!(
+        "{:<10}{:>12}{:>14}",
+        "Business",
+        monkey_business(&a),
+        monkey_business(&b)
+    ));
+
+    Ok(lines.join("\n"))
+}
+
+/// Steps through the rounds one monkey's turn at a time.
+#[derive(Clone)]
+pub struct MonkeySimulation {
+    monkeys: Vec<Monkey>,
+    inspections: Vec<usize>,
+    round: usize,
+    next_monkey: usize,
+    rounds: usize,
+    divide: bool,
+    mod_by: i64,
+}
+
+impl MonkeySimulation {
+    pub fn new(input: &str, rounds: usize, divide: bool) -> ParseResult<Self> {
+        let monkeys = parse_input(input)?;
+
+        Ok(Self {
+            inspections: vec![0; monkeys.len()],
+            mod_by: common_modulus(&monkeys),
+            monkeys,
+            round: 0,
+            next_monkey: 0,
+            rounds,
+            divide,
+        })
+    }
+}
+
+impl Simulation for MonkeySimulation {
+    fn step(&mut self) -> bool {
+        if self.round == self.rounds || self.monkeys.is_empty() {
+            return false;
+        }
+
+        let mut outbound_items = HashMap::new();
+        let monkey = &mut self.monkeys[self.next_monkey];
+        self.inspections[self.next_monkey] +=
+            monkey.process_turn(&mut outbound_items, self.divide, self.mod_by);
+
+        for (monkey, items) in outbound_items {
+            self.monkeys[monkey].items.extend(items);
+        }
+
+        self.next_monkey += 1;
+
+        if self.next_monkey == self.monkeys.len() {
+            self.next_monkey = 0;
+            self.round += 1;
+        }
+
+        true
+    }
+
+    fn render(&self) -> String {
+        let mut lines = vec![if self.round == self.rounds {
+            format!("All {} rounds done", self.rounds)
+        } else {
+            format!(
+                "Round {}/{}, monkey {} to play",
+                self.round + 1,
+                self.rounds,
+                self.next_monkey
+            )
+        }];
+
+        for (i, monkey) in self.monkeys.iter().enumerate() {
+            lines.push(format!(
+                "{} Monkey {} (inspected {}): {}",
+                if i == self.next_monkey { ">" } else { " " },
+                i,
+                self.inspections[i],
+                monkey.items.iter().join(", ")
+            ));
+        }
+
+        lines.join("\n")
+    }
+}
+
+pub fn day11a(input: &str) -> ParseResult<String> {
+    let monkeys = parse_input(input)?;
+    Ok(process_rounds(monkeys, 20, true))
+}
+
+pub fn day11b(input: &str) -> ParseResult<String> {
+    let monkeys = parse_input(input)?;
+    Ok(process_rounds(monkeys, 10_000, false))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3
+
+Monkey 1:
+  Starting items: 54, 65, 75, 74
+  Operation: new = old + 6
+  Test: divisible by 19
+    If true: throw to monkey 2
+    If false: throw to monkey 0
+
+Monkey 2:
+  Starting items: 79, 60, 97
+  Operation: new = old * old
+  Test: divisible by 13
+    If true: throw to monkey 1
+    If false: throw to monkey 3
+
+Monkey 3:
+  Starting items: 74
+  Operation: new = old + 3
+  Test: divisible by 17
+    If true: throw to monkey 0
+    If false: throw to monkey 1
+";
+
+    #[test]
+    fn labels_unfinished_rounds() {
+        assert_eq!(day11a(EXAMPLE).unwrap(), "10605");
+
+        let token = cancel::Token::default();
+        token.cancel();
+
+        let partial = std::thread::spawn(move || {
+            cancel::install(token);
+            day11b(EXAMPLE).unwrap()
+        });
+
+        assert_eq!(partial.join().unwrap(), "0 after 0 of 10000 rounds");
+    }
+}
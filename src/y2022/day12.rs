@@ -0,0 +1,265 @@
+use nalgebra::Vector2;
+
+use crate::{
+    parse::{ParseError, ParseResult},
+    search,
+};
+
+type Pos = Vector2<isize>;
+
+struct Heightmap {
+    data: Vec<u8>,
+    width: usize,
+    height: usize,
+}
+
+struct PointsOfInterest {
+    start: Pos,
+    destination: Pos,
+}
+
+impl Heightmap {
+    fn get(&self, pos: Pos) -> u8 {
+        self.data[(pos.y as usize * self.width + pos.x as usize) as usize]
+    }
+
+    fn is_out_of_bounds(&self, pos: Pos) -> bool {
+        pos.x < 0 || pos.y < 0 || pos.x >= self.width as isize || pos.y >= self.height as isize
+    }
+
+    /// The squares that can be stepped to from `pos`, at most one higher.
+    fn get_legal_neighbors(&self, pos: Pos, out: &mut Vec<Pos>) {
+        self.get_neighbors_where(pos, out, |from, to| to <= from + 1);
+    }
+
+    /// The squares that `pos` can be stepped to from, at most one lower.
+    fn get_legal_predecessors(&self, pos: Pos, out: &mut Vec<Pos>) {
+        self.get_neighbors_where(pos, out, |from, to| from <= to + 1);
+    }
+
+    fn get_neighbors_where(&self, pos: Pos, out: &mut Vec<Pos>, legal: impl Fn(u8, u8) -> bool) {
+        out.clear();
+
+        let current_height = self.get(pos);
+
+        let left = pos + Vector2::new(-1, 0);
+        let right = pos + Vector2::new(1, 0);
+        let up = pos + Vector2::new(0, -1);
+        let down = pos + Vector2::new(0, 1);
+
+        let neighbors = [left, right, up, down];
+
+        for neighbor in neighbors.iter() {
+            if self.is_out_of_bounds(*neighbor) {
+                continue;
+            }
+
+            if !legal(current_height, self.get(*neighbor)) {
+                continue;
+            }
+
+            out.push(*neighbor);
+        }
+    }
+
+    /// A* towards `end`, guided by the Manhattan distance, which never overestimates as every
+    /// step moves one square.
+    fn find_path(&self, start: Pos, end: Pos) -> Option<Vec<Pos>> {
+        let mut neighbors = Vec::new();
+
+        let (path, _) = search::astar(
+            start,
+            |pos, out| {
+                self.get_legal_neighbors(*pos, &mut neighbors);
+                out.extend(neighbors.iter().map(|neighbor| (*neighbor, 1)));
+            },
+            |pos| (end - pos).abs().sum(),
+            |pos| *pos == end,
+        )?;
+
+        Some(path)
+    }
+
+    /// Breadth-first search backwards from `end`, so that the first square of the lowest
+    /// height it reaches is the end of the shortest path from any of them.
+    fn find_path_from_lowest(&self, end: Pos) -> Option<Vec<Pos>> {
+        let mut path = search::bfs(
+            end,
+            |pos, predecessors| self.get_legal_predecessors(*pos, predecessors),
+            |pos| self.get(*pos) == 0,
+        )?;
+
+        path.reverse();
+        Some(path)
+    }
+}
+
+fn read_input(input: &str) -> ParseResult<(Heightmap, PointsOfInterest)> {
+    let width = input.lines().next().map_or(0, str::len);
+    let height = input.lines().count();
+    let mut data = vec![0; width * height];
+    let mut start_pos = None;
+    let mut destination_pos = None;
+
+    for (y, line) in input.lines().enumerate() {
+        let error = |message: String| Err(ParseError::new(message).at_line(y + 1));
+
+        if line.len() != width {
+            return error(format!("expected {} columns, got {}", width, line.len()));
+        }
+
+        for (x, c) in line.chars().enumerate() {
+            let pos = y * width + x;
+            let marker = match c {
+                'S' => &mut start_pos,
+                'E' => &mut destination_pos,
+                'a'..='z' => {
+                    data[pos] = c as u8 - 'a' as u8;
+                    continue;
+                }
+                _ => return error(format!("invalid height `{}`", c)),
+            };
+
+            if marker.is_some() {
+                return error(format!("more than one `{}`", c));
+            }
+
+            data[pos] = if c == 'S' { 0 } else { 'z' as u8 - 'a' as u8 };
+            *marker = Some(Vector2::new(x as isize, y as isize));
+        }
+    }
+
+    let start_pos = start_pos.ok_or_else(|| ParseError::new("no start `S`"))?;
+    let destination_pos = destination_pos.ok_or_else(|| ParseError::new("no destination `E`"))?;
+
+    let heightmap = Heightmap {
+        data,
+        width,
+        height,
+    };
+
+    let points_of_interest = PointsOfInterest {
+        start: start_pos,
+        destination: destination_pos,
+    };
+
+    Ok((heightmap, points_of_interest))
+}
+
+/// Entry point for fuzzing the parser.
+pub fn fuzz(input: &str) -> ParseResult<()> {
+    read_input(input).map(drop)
+}
+
+pub fn day12a(input: &str) -> ParseResult<String> {
+    let (heightmap, points_of_interest) = read_input(input)?;
+    let path = heightmap
+        .find_path(points_of_interest.start, points_of_interest.destination)
+        .ok_or_else(|| ParseError::new("no path from `S` to `E`"))?;
+
+    Ok((path.len() - 1).to_string())
+}
+
+pub fn day12b(input: &str) -> ParseResult<String> {
+    let (heightmap, points_of_interest) = read_input(input)?;
+
+    let path = heightmap
+        .find_path_from_lowest(points_of_interest.destination)
+        .ok_or_else(|| ParseError::new("no path from any `a` to `E`"))?;
+
+    Ok((path.len() - 1).to_string())
+}
+
+fn render_path(
+    heightmap: &Heightmap,
+    path: &[Pos],
+    points_of_interest: &PointsOfInterest,
+) -> String {
+    let mut output = String::new();
+
+    for y in 0..heightmap.height {
+        for x in 0..heightmap.width {
+            let pos = Vector2::new(x as isize, y as isize);
+            if pos == points_of_interest.start {
+                output.push('S');
+            } else if pos == points_of_interest.destination {
+                output.push('E');
+            } else if path.contains(&pos) {
+                output.push('x');
+            } else {
+                output.push('.');
+            }
+        }
+        output.push('\n');
+    }
+
+    output
+}
+
+/// The shortest paths of both parts drawn on the map.
+pub fn explain(input: &str) -> ParseResult<String> {
+    let (heightmap, points_of_interest) = read_input(input)?;
+    let mut output = String::new();
+
+    let paths = [
+        (
+            "From `S`",
+            heightmap.find_path(points_of_interest.start, points_of_interest.destination),
+        ),
+        (
+            "From the nearest `a`",
+            heightmap.find_path_from_lowest(points_of_interest.destination),
+        ),
+    ];
+
+    for (label, path) in paths {
+        match path {
+            Some(path) => {
+                output += &format!("{}, {} steps:\n", label, path.len() - 1);
+                output += &render_path(&heightmap, &path, &points_of_interest);
+            }
+            None => output += &format!("{}: no path\n", label),
+        }
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapshot::assert_snapshot;
+
+    const EXAMPLE: &str = "\
+Sabqponm
+abcryxxl
+accszExk
+acctuvwj
+abdefghi";
+
+    #[test]
+    fn renders_shortest_path() {
+        let (heightmap, points_of_interest) = read_input(EXAMPLE).unwrap();
+        let path = heightmap
+            .find_path(points_of_interest.start, points_of_interest.destination)
+            .unwrap();
+
+        assert_eq!(path.len() - 1, 31);
+        assert_snapshot(
+            "day12_path",
+            &render_path(&heightmap, &path, &points_of_interest),
+        );
+    }
+
+    #[test]
+    fn solves_the_example() {
+        assert_eq!(day12a(EXAMPLE).unwrap(), "31");
+        assert_eq!(day12b(EXAMPLE).unwrap(), "29");
+    }
+
+    #[test]
+    fn reports_unreachable_destination() {
+        assert!(day12a("SbE").is_err());
+        assert!(day12b("SbE").is_err());
+    }
+}
@@ -0,0 +1,262 @@
+use std::{cmp::Ordering, collections::VecDeque};
+
+use itertools::{EitherOrBoth, Itertools};
+
+use crate::parse::{self, ParseError, ParseResult};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token {
+    LBracket,
+    RBracket,
+    Comma,
+    Number(i64),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Value {
+    Number(i64),
+    List(Vec<Value>),
+}
+
+type Tokens = VecDeque<Token>;
+
+fn tokenize_line(line: &str) -> ParseResult<Tokens> {
+    let mut tokens = VecDeque::new();
+    let chars = line.chars().collect::<Vec<_>>();
+    let mut char_iter = chars.iter().copied().peekable();
+
+    while let Some(token) = char_iter.next() {
+        match token {
+            '[' => tokens.push_back(Token::LBracket),
+            ']' => tokens.push_back(Token::RBracket),
+            ',' => tokens.push_back(Token::Comma),
+            '0'..='9' => {
+                let mut number = token.to_digit(10).unwrap() as i64;
+                while let Some(digit) = char_iter.peek().and_then(|next| next.to_digit(10)) {
+                    number = number
+                        .checked_mul(10)
+                        .and_then(|number| number.checked_add(digit as i64))
+                        .ok_or_else(|| ParseError::new("number is too large"))?;
+                    char_iter.next();
+                }
+                tokens.push_back(Token::Number(number));
+            }
+            _ => return Err(ParseError::new(format!("unexpected character `{}`", token))),
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Deeper lists are rejected rather than risking a stack overflow.
+const MAX_DEPTH: usize = 256;
+
+fn pop_and_expect(tokens: &mut Tokens, expected: Token) -> ParseResult<()> {
+    match tokens.pop_front() {
+        Some(token) if token == expected => Ok(()),
+        Some(token) => Err(ParseError::new(format!(
+            "expected {:?}, got {:?}",
+            expected, token
+        ))),
+        None => Err(ParseError::new(format!(
+            "expected {:?}, got nothing",
+            expected
+        ))),
+    }
+}
+
+fn parse_list(tokens: &mut Tokens, depth: usize) -> ParseResult<Value> {
+    if depth > MAX_DEPTH {
+        return Err(ParseError::new("lists are nested too deeply"));
+    }
+
+    pop_and_expect(tokens, Token::LBracket)?;
+
+    let mut list = Vec::new();
+
+    if tokens.front() == Some(&Token::RBracket) {
+        tokens.pop_front();
+        return Ok(Value::List(list));
+    }
+
+    loop {
+        match tokens.front().copied() {
+            Some(Token::LBracket) => {
+                list.push(parse_list(tokens, depth + 1)?);
+            }
+            Some(Token::Number(number)) => {
+                tokens.pop_front();
+                list.push(Value::Number(number));
+            }
+            Some(token) => {
+                return Err(ParseError::new(format!(
+                    "expected a value, got {:?}",
+                    token
+                )))
+            }
+            None => return Err(ParseError::new("unterminated list")),
+        }
+
+        match tokens.pop_front() {
+            Some(Token::Comma) => {}
+            Some(Token::RBracket) => break,
+            Some(token) => {
+                return Err(ParseError::new(format!(
+                    "expected `,` or `]`, got {:?}",
+                    token
+                )))
+            }
+            None => return Err(ParseError::new("unterminated list")),
+        }
+    }
+
+    Ok(Value::List(list))
+}
+
+fn tokenize_and_parse(line: &str) -> ParseResult<Value> {
+    let mut tokens = tokenize_line(line)?;
+    let list = parse_list(&mut tokens, 0)?;
+
+    if let Some(token) = tokens.front() {
+        return Err(ParseError::new(format!(
+            "unexpected {:?} after the list",
+            token
+        )));
+    }
+
+    Ok(list)
+}
+
+fn parse_pairs(input: &str) -> ParseResult<Vec<(Value, Value)>> {
+    parse::parse_blocks(input, |pair| {
+        parse::parse_lines(pair, tokenize_and_parse)?
+            .into_iter()
+            .collect_tuple()
+            .ok_or_else(|| ParseError::new("expected a pair of packets"))
+    })
+}
+
+fn parse_packets(input: &str) -> ParseResult<Vec<Value>> {
+    let packets = parse::parse_lines(input, |line| {
+        (!line.is_empty())
+            .then(|| tokenize_and_parse(line))
+            .transpose()
+    })?;
+
+    Ok(packets.into_iter().flatten().collect())
+}
+
+/// Entry point for fuzzing the parser.
+pub fn fuzz(input: &str) -> ParseResult<()> {
+    parse_pairs(input)?;
+    parse_packets(input).map(drop)
+}
+
+fn compare_values(left: &Value, right: &Value) -> Ordering {
+    match (left, right) {
+        (Value::Number(left), Value::Number(right)) => left.cmp(right),
+        (Value::List(left), Value::List(right)) => {
+            for pair in left.iter().zip_longest(right.iter()) {
+                match pair {
+                    EitherOrBoth::Both(l, r) => match compare_values(l, r) {
+                        Ordering::Less => return Ordering::Less,
+                        Ordering::Greater => return Ordering::Greater,
+                        Ordering::Equal => (),
+                    },
+                    EitherOrBoth::Left(_) => return Ordering::Greater,
+                    EitherOrBoth::Right(_) => return Ordering::Less,
+                };
+            }
+
+            Ordering::Equal
+        }
+        (Value::Number(left), right @ Value::List(_)) => {
+            let left = Value::List(vec![Value::Number(*left)]);
+            compare_values(&left, right)
+        }
+        (left @ Value::List(_), Value::Number(right)) => {
+            let right = Value::List(vec![Value::Number(*right)]);
+            compare_values(left, &right)
+        }
+    }
+}
+
+/// How every pair compares. Part a sums the numbers of the pairs in the right order.
+pub fn explain(input: &str) -> ParseResult<String> {
+    let pairs = parse_pairs(input)?;
+    let mut lines = Vec::new();
+    let mut sum = 0;
+
+    for (i, (left, right)) in pairs.iter().enumerate() {
+        let outcome = match compare_values(left, right) {
+            Ordering::Less => {
+                sum += i + 1;
+                "right order"
+            }
+            Ordering::Greater => "wrong order",
+            Ordering::Equal => "equal",
+        };
+
+        lines.push(format!("Pair {}: {}", i + 1, outcome));
+        lines.push(format!("  {}", stringify_list(left)));
+        lines.push(format!("  {}", stringify_list(right)));
+    }
+
+    lines.push(format!("Sum of the pairs in the right order: {}", sum));
+
+    Ok(lines.join("\n"))
+}
+
+pub fn day13a(input: &str) -> ParseResult<String> {
+    let input = parse_pairs(input)?;
+
+    let result = input
+        .into_iter()
+        .enumerate()
+        .map(|(i, (left, right))| {
+            if compare_values(&left, &right) == Ordering::Less {
+                i + 1
+            } else {
+                0
+            }
+        })
+        .sum::<usize>();
+
+    Ok(result.to_string())
+}
+
+pub fn day13b(input: &str) -> ParseResult<String> {
+    let mut input = parse_packets(input)?;
+
+    let marker1 = tokenize_and_parse("[[2]]")?;
+    let marker2 = tokenize_and_parse("[[6]]")?;
+
+    input.push(marker1.clone());
+    input.push(marker2.clone());
+
+    input.sort_by(compare_values);
+
+    let marker1_pos = input.iter().position(|x| x == &marker1).unwrap() + 1;
+    let marker2_pos = input.iter().position(|x| x == &marker2).unwrap() + 1;
+
+    let decoder_key = marker1_pos * marker2_pos;
+    Ok(decoder_key.to_string())
+}
+
+fn stringify_list(list: &Value) -> String {
+    match list {
+        Value::Number(number) => number.to_string(),
+        Value::List(list) => {
+            let mut string = String::new();
+            string.push('[');
+            for (i, item) in list.iter().enumerate() {
+                if i > 0 {
+                    string.push(',');
+                }
+                string.push_str(&stringify_list(item));
+            }
+            string.push(']');
+            string
+        }
+    }
+}
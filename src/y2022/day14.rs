@@ -0,0 +1,335 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+use log::info;
+use nalgebra::Vector2;
+
+use crate::{
+    cancel,
+    debugger::Simulation,
+    parse::{self, ParseError, ParseResult},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Cell {
+    Wall,
+    Sand,
+}
+
+type Pos = Vector2<i32>;
+
+#[derive(Clone)]
+struct SparseGrid {
+    data: HashMap<Pos, Cell>,
+    moving_sand_pos: Pos,
+    has_floor: bool,
+    max_y: i32,
+}
+
+impl SparseGrid {
+    const SPAWN_POS: Pos = Pos::new(500, 0);
+
+    fn new(has_floor: bool) -> SparseGrid {
+        SparseGrid {
+            data: HashMap::new(),
+            moving_sand_pos: Self::SPAWN_POS,
+            has_floor,
+            max_y: i32::MIN,
+        }
+    }
+
+    fn get(&self, pos: Pos) -> Option<Cell> {
+        if let Some(cell) = self.data.get(&pos) {
+            Some(*cell)
+        } else if self.has_floor && pos.y >= self.max_y + 2 {
+            Some(Cell::Wall)
+        } else {
+            None
+        }
+    }
+
+    fn set(&mut self, pos: Pos, cell: Cell) {
+        self.data.insert(pos, cell);
+
+        // Only update bounds if we're setting a wall
+        // Otherwise floor moves down constantly
+        if cell == Cell::Wall {
+            self.max_y = self.max_y.max(pos.y);
+        }
+    }
+
+    fn set_sand_pos(&mut self, pos: Pos) {
+        self.moving_sand_pos = pos;
+    }
+
+    fn is_sand_falling_infinitely(&self) -> bool {
+        self.moving_sand_pos.y > self.max_y
+    }
+
+    fn is_spawn_blocked(&self) -> bool {
+        self.get(SparseGrid::SPAWN_POS) == Some(Cell::Sand)
+    }
+
+    fn create_wall(&mut self, start: Pos, end: Pos) {
+        if start.x == end.x {
+            // Vertical line
+            let (min_y, max_y) = if start.y < end.y {
+                (start.y, end.y)
+            } else {
+                (end.y, start.y)
+            };
+
+            for y in min_y..=max_y {
+                self.set(Vector2::new(start.x, y), Cell::Wall);
+            }
+        } else if start.y == end.y {
+            // Horizontal line
+            let (min_x, max_x) = if start.x < end.x {
+                (start.x, end.x)
+            } else {
+                (end.x, start.x)
+            };
+
+            for x in min_x..=max_x {
+                self.set(Vector2::new(x, start.y), Cell::Wall);
+            }
+        } else {
+            unreachable!("diagonal lines are rejected when parsing");
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum UpdateResult {
+    Moved,
+    Settled,
+}
+
+fn update_grid(grid: &mut SparseGrid) -> UpdateResult {
+    let pos = grid.moving_sand_pos;
+
+    let down = pos + Vector2::new(0, 1);
+    let down_left = pos + Vector2::new(-1, 1);
+    let down_right = pos + Vector2::new(1, 1);
+
+    for neighbor in [down, down_left, down_right] {
+        match grid.get(neighbor) {
+            None => {
+                grid.set_sand_pos(neighbor);
+                return UpdateResult::Moved;
+            }
+            Some(_) => {}
+        }
+    }
+
+    grid.set(pos, Cell::Sand);
+    grid.moving_sand_pos = SparseGrid::SPAWN_POS;
+    UpdateResult::Settled
+}
+
+/// Coordinates are limited so that a single line can't take forever to draw.
+const MAX_COORDINATE: i32 = 10_000;
+
+fn parse_point(point: &str) -> ParseResult<Pos> {
+    let (x, y) = parse::split_once(point, ",")?;
+    let pos = Vector2::new(parse::number(x)?, parse::number(y)?);
+
+    if !(0..=MAX_COORDINATE).contains(&pos.x) || !(0..=MAX_COORDINATE).contains(&pos.y) {
+        return Err(ParseError::new(format!(
+            "point `{}` is out of range",
+            point
+        )));
+    }
+
+    Ok(pos)
+}
+
+fn parse_input(input: &str) -> ParseResult<Vec<Vec<Pos>>> {
+    let lines = parse::parse_lines(input, |line| {
+        let points = line
+            .split(" -> ")
+            .map(parse_point)
+            .collect::<ParseResult<Vec<_>>>()?;
+
+        for (prev, next) in points.iter().tuple_windows() {
+            if prev.x != next.x && prev.y != next.y {
+                return Err(ParseError::new(format!(
+                    "diagonal line from {},{} to {},{}",
+                    prev.x, prev.y, next.x, next.y
+                )));
+            }
+        }
+
+        Ok(points)
+    })?;
+
+    if lines.is_empty() {
+        return Err(ParseError::new("no rock paths"));
+    }
+
+    Ok(lines)
+}
+
+/// Entry point for fuzzing the parser.
+pub fn fuzz(input: &str) -> ParseResult<()> {
+    parse_input(input).map(drop)
+}
+
+fn create_grid(input: &str, has_floor: bool) -> ParseResult<SparseGrid> {
+    let mut grid = SparseGrid::new(has_floor);
+
+    for line in parse_input(input)? {
+        for (prev, next) in line.iter().tuple_windows() {
+            grid.create_wall(*prev, *next);
+        }
+    }
+
+    Ok(grid)
+}
+
+fn count_settled_sand_until(
+    input: &str,
+    has_floor: bool,
+    condition: impl Fn(&SparseGrid) -> bool,
+) -> ParseResult<usize> {
+    let token = cancel::current();
+    let mut grid = create_grid(input, has_floor)?;
+    let mut sand_settled = 0;
+
+    loop {
+        if token.is_cancelled() {
+            info!("Stopped after {} units of sand settled", sand_settled);
+            break;
+        }
+
+        let result = update_grid(&mut grid);
+
+        if result == UpdateResult::Settled {
+            sand_settled += 1;
+        }
+
+        if condition(&grid) {
+            break;
+        }
+    }
+
+    Ok(sand_settled)
+}
+
+#[derive(Clone)]
+pub struct SandSimulation {
+    grid: SparseGrid,
+    sand_settled: usize,
+}
+
+impl SandSimulation {
+    pub fn new(input: &str, has_floor: bool) -> ParseResult<Self> {
+        Ok(Self {
+            grid: create_grid(input, has_floor)?,
+            sand_settled: 0,
+        })
+    }
+
+    fn is_finished(&self) -> bool {
+        if self.grid.has_floor {
+            self.grid.is_spawn_blocked()
+        } else {
+            self.grid.is_sand_falling_infinitely()
+        }
+    }
+}
+
+impl Simulation for SandSimulation {
+    fn step(&mut self) -> bool {
+        if self.is_finished() {
+            return false;
+        }
+
+        if update_grid(&mut self.grid) == UpdateResult::Settled {
+            self.sand_settled += 1;
+        }
+
+        true
+    }
+
+    fn render(&self) -> String {
+        format!(
+            "{}\n\nSand settled: {}",
+            render_grid(&self.grid),
+            self.sand_settled
+        )
+    }
+}
+
+pub fn day14a(input: &str) -> ParseResult<String> {
+    let sand_settled =
+        count_settled_sand_until(input, false, |grid| grid.is_sand_falling_infinitely())?;
+    Ok(sand_settled.to_string())
+}
+
+pub fn day14b(input: &str) -> ParseResult<String> {
+    let sand_settled = count_settled_sand_until(input, true, |grid| grid.is_spawn_blocked())?;
+    Ok(sand_settled.to_string())
+}
+
+fn render_grid(grid: &SparseGrid) -> String {
+    let mut min_y = grid.data.keys().map(|pos| pos.y).min().unwrap();
+    let mut max_y = grid.data.keys().map(|pos| pos.y).max().unwrap() + 1;
+    let mut max_x = grid.data.keys().map(|pos| pos.x).max().unwrap() + 2;
+    let mut min_x = grid.data.keys().map(|pos| pos.x).min().unwrap() - 2;
+
+    let sand_pos = grid.moving_sand_pos;
+    let mut output = String::new();
+
+    min_y = min_y.min(sand_pos.y);
+    max_y = max_y.max(sand_pos.y);
+    max_x = max_x.max(sand_pos.x);
+    min_x = min_x.min(sand_pos.x);
+
+    for y in min_y..=max_y {
+        for x in min_x..=max_x {
+            let pos = Vector2::new(x, y);
+
+            if sand_pos == pos {
+                output.push('+');
+                continue;
+            }
+
+            let cell = grid.get(pos);
+
+            let ch = match cell {
+                Some(Cell::Wall) => '#',
+                Some(Cell::Sand) => 'o',
+                None => '.',
+            };
+
+            output.push(ch);
+        }
+
+        output.push('\n');
+    }
+
+    output.pop();
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapshot::assert_snapshot;
+
+    const EXAMPLE: &str = "\
+498,4 -> 498,6 -> 496,6
+503,4 -> 502,4 -> 502,9 -> 494,9";
+
+    #[test]
+    fn renders_settled_sand() {
+        for (has_floor, name, settled) in [(false, "day14a_grid", 24), (true, "day14b_grid", 93)] {
+            let mut simulation = SandSimulation::new(EXAMPLE, has_floor).unwrap();
+            while simulation.step() {}
+
+            assert_eq!(simulation.sand_settled, settled);
+            assert_snapshot(name, &render_grid(&simulation.grid));
+        }
+    }
+}
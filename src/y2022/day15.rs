@@ -0,0 +1,204 @@
+use fnv::FnvHashSet;
+use nalgebra::Vector2;
+use once_cell::sync::OnceCell;
+use rayon::prelude::*;
+use regex::Regex;
+
+use crate::{
+    cancel,
+    parse::{self, ParseError, ParseResult},
+};
+
+type Pos = Vector2<i64>;
+
+#[derive(Debug)]
+struct SensorAndBacon {
+    sensor: Pos,
+    beacon: Pos,
+
+    distance: i64,
+}
+
+/// Keeps the distance computations far away from overflowing.
+const MAX_COORDINATE: i64 = 1 << 40;
+
+fn parse_line(line: &str) -> ParseResult<SensorAndBacon> {
+    static REGEX: OnceCell<Regex> = OnceCell::new();
+
+    let regex = REGEX.get_or_init(|| {
+        regex::Regex::new(
+            r"^Sensor at x=(-?\d+), y=(-?\d+): closest beacon is at x=(-?\d+), y=(-?\d+)$",
+        )
+        .unwrap()
+    });
+    let captures = regex
+        .captures(line)
+        .ok_or_else(|| ParseError::new(format!("invalid sensor `{}`", line)))?;
+
+    let coordinate = |i| -> ParseResult<i64> {
+        let coordinate: i64 = parse::number(&captures[i])?;
+
+        if coordinate.abs() > MAX_COORDINATE {
+            return Err(ParseError::new(format!(
+                "coordinate {} is out of range",
+                coordinate
+            )));
+        }
+
+        Ok(coordinate)
+    };
+
+    let sensor = Pos::new(coordinate(1)?, coordinate(2)?);
+    let beacon = Pos::new(coordinate(3)?, coordinate(4)?);
+
+    let distance = manhattan_distance(sensor, beacon);
+
+    Ok(SensorAndBacon {
+        sensor,
+        beacon,
+        distance,
+    })
+}
+
+fn manhattan_distance(a: Pos, b: Pos) -> i64 {
+    (a.x - b.x).abs() + (a.y - b.y).abs()
+}
+
+fn is_covered_by_sensor(pos: Pos, sensors: &[SensorAndBacon]) -> bool {
+    for sensor in sensors {
+        let distance_to_sensor = manhattan_distance(pos, sensor.sensor);
+
+        if distance_to_sensor <= sensor.distance {
+            return true;
+        }
+    }
+
+    false
+}
+
+fn parse_input(input: &str) -> ParseResult<Vec<SensorAndBacon>> {
+    parse::parse_lines(input, parse_line)
+}
+
+/// Entry point for fuzzing the parser.
+pub fn fuzz(input: &str) -> ParseResult<()> {
+    parse_input(input).map(drop)
+}
+
+fn edge_points(sensor: &SensorAndBacon) -> impl IntoParallelIterator<Item = Pos> {
+    let pos = sensor.sensor;
+    let distance = sensor.distance + 1;
+
+    let bound_max = Pos::new(4_000_000, 4_000_000);
+
+    (0..distance)
+        .into_par_iter()
+        .flat_map(move |x| {
+            let y = distance - x;
+            [
+                pos + Pos::new(x, y),
+                pos + Pos::new(x, -y),
+                pos + Pos::new(-x, y),
+                pos + Pos::new(-x, -y),
+            ]
+        })
+        .filter(move |point| {
+            point.x >= 0 && point.y >= 0 && point.x <= bound_max.x && point.y <= bound_max.y
+        })
+}
+
+fn points_on_line(y: i64, sensor: &SensorAndBacon) -> Vec<Pos> {
+    let mut points = Vec::new();
+
+    for x in -sensor.distance..=sensor.distance {
+        let this_point = Vector2::new(sensor.sensor.x + x, y);
+
+        if this_point == sensor.beacon {
+            continue;
+        }
+
+        let distance_to_sensor = manhattan_distance(this_point, sensor.sensor);
+
+        if distance_to_sensor <= sensor.distance {
+            points.push(this_point);
+        }
+    }
+
+    points
+}
+
+/// The radius of every sensor, i.e. the distance to its beacon, and how much of part a's row
+/// it covers.
+pub fn explain(input: &str) -> ParseResult<String> {
+    let sensors = parse_input(input)?;
+    let row = 2_000_000;
+
+    let lines = sensors.iter().map(|sensor| {
+        let reach = sensor.distance - (sensor.sensor.y - row).abs();
+        let coverage = if reach < 0 {
+            format!("doesn't reach y={}", row)
+        } else {
+            format!(
+                "covers x={}..={} on y={}",
+                sensor.sensor.x - reach,
+                sensor.sensor.x + reach,
+                row
+            )
+        };
+
+        format!(
+            "Sensor at x={}, y={}: beacon at x={}, y={}, radius {}, {}",
+            sensor.sensor.x,
+            sensor.sensor.y,
+            sensor.beacon.x,
+            sensor.beacon.y,
+            sensor.distance,
+            coverage
+        )
+    });
+
+    Ok(lines.collect::<Vec<_>>().join("\n"))
+}
+
+pub fn day15a(input: &str) -> ParseResult<String> {
+    let input = parse_input(input)?;
+
+    let result = {
+        let input: &[SensorAndBacon] = &input;
+
+        let covered_positions = input
+            .par_iter()
+            .flat_map(|sensor| points_on_line(2_000_000, sensor))
+            .collect::<FnvHashSet<_>>();
+
+        covered_positions.len()
+    };
+
+    Ok(result.to_string())
+}
+
+pub fn day15b(input: &str) -> ParseResult<String> {
+    let input = parse_input(input)?;
+    let token = cancel::current();
+
+    let uncovered_point = input
+        .par_iter()
+        .flat_map(edge_points)
+        .find_any(|point| {
+            if token.is_cancelled() {
+                true
+            } else if is_covered_by_sensor(*point, &input) {
+                false
+            } else {
+                true
+            }
+        })
+        .ok_or_else(|| ParseError::new("every point in range is covered by a sensor"))?;
+
+    // The scan has no partial answer, the point it stopped at is arbitrary
+    if token.is_cancelled() {
+        return Err(ParseError::new("cancelled"));
+    }
+
+    Ok((uncovered_point.x * 4000000 + uncovered_point.y).to_string())
+}
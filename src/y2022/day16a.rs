@@ -1,21 +1,23 @@
-use std::{fmt::Formatter, sync::Arc, time::Instant};
+use std::fmt::Formatter;
 
-use arrayvec::ArrayVec;
-use dashmap::DashMap;
-use fnv::{FnvBuildHasher, FnvHashMap};
+use fnv::FnvHashMap;
 use itertools::Itertools;
+use log::debug;
 use once_cell::sync::OnceCell;
-use rayon::prelude::*;
 use regex::Regex;
 
-const INPUT: &str = include_str!("./day16.input");
+use crate::{
+    cancel,
+    parse::{self, ParseError, ParseResult},
+    search::{BranchAndBound, Incumbent, Problem},
+};
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
 struct ValveId(u8);
 
 #[derive(Debug, Hash, Clone, PartialEq, Eq)]
 struct Valve {
-    name: &'static str,
+    name: String,
     id: ValveId,
     flow_rate: u8,
     tunnels: Vec<ValveId>,
@@ -23,7 +25,7 @@ struct Valve {
 
 type Valves = FnvHashMap<ValveId, Valve>;
 
-fn parse_input() -> (FnvHashMap<ValveId, Valve>, ValveId, usize) {
+fn parse_line(line: &str) -> ParseResult<(&str, u8, Vec<&str>)> {
     static REGEX: OnceCell<Regex> = OnceCell::new();
 
     let regex = REGEX.get_or_init(|| {
@@ -33,44 +35,78 @@ fn parse_input() -> (FnvHashMap<ValveId, Valve>, ValveId, usize) {
         .unwrap()
     });
 
+    let captures = regex
+        .captures(line)
+        .ok_or_else(|| ParseError::new(format!("invalid valve `{}`", line)))?;
+
+    let name = captures.get(1).unwrap().as_str();
+    let flow_rate: u8 = parse::number(captures.get(2).unwrap().as_str())?;
+    let tunnels = captures.get(3).unwrap().as_str().split(", ").collect_vec();
+
+    Ok((name, flow_rate, tunnels))
+}
+
+/// Valves with a flow rate get the ids 1 to 31, so that they fit in a [`BitVec32`]. The rest
+/// are numbered from 32 up.
+fn next_valve_id(
+    flow_rate: u8,
+    useful_valve_id: &mut u8,
+    useless_valve_id: &mut u8,
+) -> ParseResult<ValveId> {
+    let next_id = if flow_rate == 0 {
+        useless_valve_id
+    } else if *useful_valve_id < 32 {
+        useful_valve_id
+    } else {
+        return Err(ParseError::new("more than 31 valves with a flow rate"));
+    };
+
+    let id = ValveId(*next_id);
+    *next_id = next_id
+        .checked_add(1)
+        .ok_or_else(|| ParseError::new("too many valves"))?;
+
+    Ok(id)
+}
+
+/// Entry point for fuzzing the parser.
+pub fn fuzz(input: &str) -> ParseResult<()> {
+    parse_input(input).map(drop)
+}
+
+fn parse_input(input: &str) -> ParseResult<(FnvHashMap<ValveId, Valve>, ValveId, usize)> {
     let mut useful_valve_id = 1;
     let mut useless_valve_id = 32;
 
     let mut name_to_id_map = FnvHashMap::default();
     let mut valves = FnvHashMap::default();
 
-    let mut parsed_lines = INPUT
-        .lines()
-        .map(|line| {
-            let captures = regex.captures(line).unwrap();
-            let name = captures.get(1).unwrap().as_str();
-            let flow_rate: u8 = captures.get(2).unwrap().as_str().parse().unwrap();
-            let tunnels = captures.get(3).unwrap().as_str().split(", ").collect_vec();
-
-            if flow_rate == 0 {
-                let id = ValveId(useless_valve_id);
-                useless_valve_id += 1;
-                name_to_id_map.insert(name, id);
-                (id, name, flow_rate, tunnels)
-            } else {
-                let id = ValveId(useful_valve_id);
-                useful_valve_id += 1;
-                name_to_id_map.insert(name, id);
-                (id, name, flow_rate, tunnels)
-            }
-        })
-        .collect_vec();
+    let mut parsed_lines = parse::parse_lines(input, |line| {
+        let (name, flow_rate, tunnels) = parse_line(line)?;
+        let id = next_valve_id(flow_rate, &mut useful_valve_id, &mut useless_valve_id)?;
+
+        if name_to_id_map.insert(name, id).is_some() {
+            return Err(ParseError::new(format!("valve {} defined twice", name)));
+        }
+
+        Ok((id, name, flow_rate, tunnels))
+    })?;
 
     // Resolve tunnels
 
     for (id, name, flow_rate, tunnels) in parsed_lines.iter_mut() {
         let tunnels = tunnels
             .iter()
-            .map(|tunnel| *name_to_id_map.get(tunnel).unwrap())
-            .collect_vec();
+            .map(|tunnel| {
+                name_to_id_map
+                    .get(tunnel)
+                    .copied()
+                    .ok_or_else(|| ParseError::new(format!("unknown valve {}", tunnel)))
+            })
+            .collect::<ParseResult<Vec<_>>>()?;
 
         let valve = Valve {
-            name,
+            name: name.to_string(),
             id: *id,
             flow_rate: *flow_rate,
             tunnels,
@@ -79,9 +115,11 @@ fn parse_input() -> (FnvHashMap<ValveId, Valve>, ValveId, usize) {
         valves.insert(*id, valve);
     }
 
-    let aa_id = *name_to_id_map.get("AA").unwrap();
+    let aa_id = *name_to_id_map
+        .get("AA")
+        .ok_or_else(|| ParseError::new("no valve AA"))?;
 
-    (valves, aa_id, (useful_valve_id as usize) - 1)
+    Ok((valves, aa_id, (useful_valve_id as usize) - 1))
 }
 
 #[derive(Clone, Hash, PartialEq, Eq)]
@@ -135,6 +173,24 @@ mod tests {
         assert_eq!(bit_vec.get(2), true);
         assert_eq!(bit_vec.get(3), false);
     }
+
+    const EXAMPLE: &str = "\
+Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
+Valve BB has flow rate=13; tunnels lead to valves CC, AA
+Valve CC has flow rate=2; tunnels lead to valves DD, BB
+Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE
+Valve EE has flow rate=3; tunnels lead to valves FF, DD
+Valve FF has flow rate=0; tunnels lead to valves EE, GG
+Valve GG has flow rate=0; tunnels lead to valves FF, HH
+Valve HH has flow rate=22; tunnel leads to valve GG
+Valve II has flow rate=0; tunnels lead to valves AA, JJ
+Valve JJ has flow rate=21; tunnel leads to valve II
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(day16a(EXAMPLE).unwrap(), "1651");
+    }
 }
 
 #[derive(Debug, Clone, Copy, Hash)]
@@ -249,70 +305,70 @@ fn create_useful_valves_mask(valves: &Valves) -> BitVec32 {
     mask
 }
 
-fn solve_a(
-    valves: &FnvHashMap<ValveId, Valve>,
-    state: State,
-    score_cache: Arc<DashMap<State, u16, FnvBuildHasher>>,
-) -> u16 {
-    static USEFUL_VALVES_MASK: OnceCell<BitVec32> = OnceCell::new();
+struct Cave {
+    valves: Valves,
+    useful_valves_mask: BitVec32,
+}
 
-    if state.time == 31 {
-        return state.released_pressure;
-    }
+impl Problem for Cave {
+    type State = State;
+    type Score = u16;
 
-    let useful_valves_mask = USEFUL_VALVES_MASK.get_or_init(|| create_useful_valves_mask(valves));
+    fn leaf_score(&self, state: &State) -> Option<u16> {
+        if state.time == TOTAL_ROUNDS {
+            return Some(state.released_pressure);
+        }
 
-    if (state.open_valves.bits ^ useful_valves_mask.bits) == 0 {
-        // Simulate to end
-        return state.released_pressure + (31 - state.time) as u16 * state.flow_rate(valves);
-    }
+        if (state.open_valves.bits ^ self.useful_valves_mask.bits) == 0 {
+            // Simulate to end
+            return Some(
+                state.released_pressure
+                    + (TOTAL_ROUNDS - state.time) as u16 * state.flow_rate(&self.valves),
+            );
+        }
 
-    if let Some(best_score) = score_cache.get(&state) {
-        return *best_score;
+        None
     }
 
-    let actions = {
-        let mut actions = ArrayVec::<Action, 6>::new();
+    fn upper_bound(&self, state: &State) -> Option<u16> {
+        Some(state.futher_score_upper_bound(&self.valves, TOTAL_ROUNDS))
+    }
 
-        let current_valve = valves.get(&state.current_valve).unwrap();
+    fn branch(&self, state: &State, out: &mut Vec<State>) {
+        let current_valve = self.valves.get(&state.current_valve).unwrap();
 
         if current_valve.flow_rate > 0 && !state.is_valve_open(state.current_valve) {
-            actions.push(Action::Open);
+            out.push(state.perform_actions(Action::Open, None, &self.valves));
         }
 
         for tunnel in &current_valve.tunnels {
-            actions.push(Action::Move(*tunnel));
+            out.push(state.perform_actions(Action::Move(*tunnel), None, &self.valves));
         }
-
-        actions
-    };
-
-    let max_pressure = actions
-        .par_iter()
-        .copied()
-        .map(|action| {
-            let new_state = state.perform_actions(action, None, valves);
-            let new_state_score = solve_a(valves, new_state.clone(), score_cache.clone());
-            new_state_score
-        })
-        .max()
-        .unwrap();
-
-    score_cache.insert(state, max_pressure);
-
-    max_pressure
+    }
 }
 
-pub fn day16a() {
-    let start_time = Instant::now();
+const TOTAL_ROUNDS: u8 = 31;
+
+pub fn day16a(input: &str) -> ParseResult<String> {
+    let (valves, initial_valve, _) = parse_input(input)?;
 
-    let (input, initial_valve, _) = parse_input();
+    let cave = Cave {
+        useful_valves_mask: create_useful_valves_mask(&valves),
+        valves,
+    };
 
-    let transition_cache: DashMap<State, u16, FnvBuildHasher> = DashMap::default();
-    let transition_cache = Arc::new(transition_cache);
+    let token = cancel::current();
+    let incumbent = Incumbent::default();
     let initial_state = State::create_initial(initial_valve, None);
-    let result = solve_a(&input, initial_state.clone(), transition_cache);
+    let search = BranchAndBound::new(&cave, &incumbent)
+        .memoize()
+        .cancellable(token.clone());
+    let result = search.solve(initial_state);
+    debug!("Branch-and-bound: {}", search.stats());
+
+    if token.is_cancelled() {
+        return Ok(incumbent.get().to_string());
+    }
 
-    println!("Day 16a: {}", result);
-    println!("Time: {:?}", start_time.elapsed());
+    Ok(result.unwrap().to_string())
 }
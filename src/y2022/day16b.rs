@@ -0,0 +1,635 @@
+use std::{
+    cmp::Reverse,
+    fmt::{Display, Formatter},
+    sync::mpsc::{self, RecvTimeoutError},
+    thread,
+    time::Duration,
+};
+
+use arrayvec::ArrayVec;
+use fnv::FnvHashMap;
+use itertools::Itertools;
+use log::{debug, log_enabled, Level};
+use once_cell::sync::OnceCell;
+use petgraph::{
+    dot::Dot,
+    prelude::UnGraph,
+    visit::{EdgeRef, IntoNodeReferences},
+};
+use regex::Regex;
+
+use crate::{
+    cancel,
+    parse::{self, ParseError, ParseResult},
+    search::{self, BranchAndBound, Incumbent, Problem},
+};
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+struct ValveId(u8);
+
+#[derive(Debug, Hash, Clone, PartialEq, Eq)]
+struct Valve {
+    name: String,
+    id: ValveId,
+    flow_rate: u8,
+    tunnels: Vec<(ValveId, u8)>,
+}
+
+impl Display for Valve {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.name, self.flow_rate)
+    }
+}
+
+type Valves = FnvHashMap<ValveId, Valve>;
+
+fn parse_line(line: &str) -> ParseResult<(&str, u8, Vec<&str>)> {
+    static REGEX: OnceCell<Regex> = OnceCell::new();
+
+    let regex = REGEX.get_or_init(|| {
+        regex::Regex::new(
+            r"^Valve ([A-Z]{2}) has flow rate=(\d+); tunnels? leads? to valves? ([A-Z]{2}(, [A-Z]{2})*)$",
+        )
+        .unwrap()
+    });
+
+    let captures = regex
+        .captures(line)
+        .ok_or_else(|| ParseError::new(format!("invalid valve `{}`", line)))?;
+
+    let name = captures.get(1).unwrap().as_str();
+    let flow_rate: u8 = parse::number(captures.get(2).unwrap().as_str())?;
+    let tunnels = captures.get(3).unwrap().as_str().split(", ").collect_vec();
+
+    Ok((name, flow_rate, tunnels))
+}
+
+/// Valves with a flow rate get the ids 1 to 31, so that they fit in a [`BitVec32`]. The rest
+/// are numbered from 32 up.
+fn next_valve_id(
+    flow_rate: u8,
+    useful_valve_id: &mut u8,
+    useless_valve_id: &mut u8,
+) -> ParseResult<ValveId> {
+    let next_id = if flow_rate == 0 {
+        useless_valve_id
+    } else if *useful_valve_id < 32 {
+        useful_valve_id
+    } else {
+        return Err(ParseError::new("more than 31 valves with a flow rate"));
+    };
+
+    let id = ValveId(*next_id);
+    *next_id = next_id
+        .checked_add(1)
+        .ok_or_else(|| ParseError::new("too many valves"))?;
+
+    Ok(id)
+}
+
+/// Entry point for fuzzing the parser.
+pub fn fuzz(input: &str) -> ParseResult<()> {
+    parse_input(input).map(drop)
+}
+
+fn parse_input(input: &str) -> ParseResult<(Valves, ValveId, usize)> {
+    let mut useful_valve_id = 1;
+    let mut useless_valve_id = 32;
+
+    let mut graph = UnGraph::<Valve, u8>::default();
+
+    let mut name_to_node_index_map = FnvHashMap::default();
+    let mut name_to_valve_id_map = FnvHashMap::default();
+
+    let parsed_lines = parse::parse_lines(input, |line| {
+        let (name, flow_rate, tunnels) = parse_line(line)?;
+        let id = next_valve_id(flow_rate, &mut useful_valve_id, &mut useless_valve_id)?;
+
+        if name_to_valve_id_map.contains_key(name) {
+            return Err(ParseError::new(format!("valve {} defined twice", name)));
+        }
+
+        let node_index = graph.add_node(Valve {
+            name: name.to_string(),
+            id,
+            flow_rate,
+            tunnels: Vec::new(),
+        });
+
+        name_to_node_index_map.insert(name, node_index);
+        name_to_valve_id_map.insert(name, id);
+
+        Ok((name, node_index, tunnels))
+    })?;
+
+    // Resolve tunnels
+
+    for (_, node_index, tunnels) in parsed_lines {
+        for tunnel in tunnels {
+            let tunnel_node_index = *name_to_node_index_map
+                .get(tunnel)
+                .ok_or_else(|| ParseError::new(format!("unknown valve {}", tunnel)))?;
+
+            if !graph.find_edge(node_index, tunnel_node_index).is_some() {
+                graph.add_edge(node_index, tunnel_node_index, 1);
+            }
+        }
+    }
+
+    // Remove useless valves (flow rate = 0)
+    // When a node is removed, we need to connect all its neighbors to each other with a cost of N + 1
+
+    loop {
+        let useless_node = graph
+            .node_references()
+            .find(|(_, valve)| valve.name != "AA" && valve.flow_rate == 0);
+
+        let (node_index, _) = match useless_node {
+            None => break,
+            Some((node_index, valve)) => (node_index, valve),
+        };
+
+        let edge_references = graph.edges(node_index).collect_vec();
+
+        let mut neighbor_edge_weights = FnvHashMap::default();
+
+        for edge_reference in edge_references {
+            let weight = edge_reference.weight();
+            let neighbor = edge_reference.target();
+            neighbor_edge_weights.insert(neighbor, *weight);
+        }
+
+        let neighbors = graph.neighbors(node_index).collect_vec();
+
+        for neighbor_1 in neighbors.iter() {
+            for neighbor_2 in neighbors.iter() {
+                if neighbor_1 == neighbor_2 {
+                    continue;
+                }
+
+                let edge = graph.find_edge(*neighbor_1, *neighbor_2);
+
+                let weight = neighbor_edge_weights[neighbor_1]
+                    .saturating_add(neighbor_edge_weights[neighbor_2]);
+
+                match edge {
+                    None => {
+                        graph.add_edge(*neighbor_1, *neighbor_2, weight);
+                    }
+                    Some(edge) if weight < graph[edge] => graph[edge] = weight,
+                    Some(_) => {}
+                }
+            }
+        }
+
+        graph.remove_node(node_index);
+    }
+
+    debug!("Compressed graph:\n{}", Dot::with_config(&graph, &[]));
+
+    let aa_id = *name_to_valve_id_map
+        .get("AA")
+        .ok_or_else(|| ParseError::new("no valve AA"))?;
+
+    // Convert graph back to a map
+
+    let mut valves = FnvHashMap::default();
+
+    for (node_index, valve) in graph.node_references() {
+        let mut valve = valve.clone();
+
+        for edge_reference in graph.edges(node_index) {
+            let neighbor = edge_reference.target();
+            let neighbor_valve = graph.node_weight(neighbor).unwrap();
+            let weight = edge_reference.weight();
+
+            valve.tunnels.push((neighbor_valve.id, *weight));
+        }
+
+        valves.insert(valve.id, valve);
+    }
+
+    Ok((valves, aa_id, (useful_valve_id - 1) as usize))
+}
+
+/// The valves left after compressing away the ones without flow, with their tunnel lengths,
+/// and the shortest distances between them.
+pub fn explain(input: &str) -> ParseResult<String> {
+    let (valves, _, _) = parse_input(input)?;
+
+    let valves = valves
+        .values()
+        .sorted_by(|a, b| a.name.cmp(&b.name))
+        .collect_vec();
+    let index_of = |id: ValveId| valves.iter().position(|valve| valve.id == id).unwrap();
+
+    let mut lines = Vec::new();
+
+    for valve in &valves {
+        let tunnels = valve
+            .tunnels
+            .iter()
+            .map(|(id, length)| (&valves[index_of(*id)].name, length))
+            .sorted()
+            .map(|(name, length)| format!("{} {}", name, length))
+            .join(", ");
+
+        lines.push(format!("{}: {}", valve, tunnels));
+    }
+
+    let distance = |from: ValveId, to: ValveId| {
+        search::dijkstra(
+            from,
+            |id, out| {
+                let tunnels = &valves[index_of(*id)].tunnels;
+                out.extend(tunnels.iter().map(|(id, length)| (*id, *length as u32)));
+            },
+            |id| *id == to,
+        )
+        .map(|(_, distance)| distance)
+    };
+
+    let distances = valves
+        .iter()
+        .map(|from| {
+            valves
+                .iter()
+                .map(|to| distance(from.id, to.id))
+                .collect_vec()
+        })
+        .collect_vec();
+
+    lines.push(String::new());
+    lines.push(format!(
+        "  {}",
+        valves
+            .iter()
+            .map(|valve| format!("{:>4}", valve.name))
+            .join("")
+    ));
+
+    for (valve, row) in valves.iter().zip(&distances) {
+        let row = row
+            .iter()
+            .map(|distance| match distance {
+                Some(distance) => format!("{:>4}", distance),
+                None => "   -".to_string(),
+            })
+            .join("");
+
+        lines.push(format!("{}{}", valve.name, row));
+    }
+
+    Ok(lines.join("\n"))
+}
+
+#[derive(Clone, Hash, PartialEq, Eq)]
+struct BitVec32 {
+    bits: u32,
+}
+
+impl BitVec32 {
+    fn new() -> Self {
+        Self { bits: 0 }
+    }
+
+    fn set(&mut self, index: usize, value: bool) {
+        if value {
+            self.bits |= 1 << index;
+        } else {
+            self.bits &= !(1 << index);
+        }
+    }
+
+    fn get(&self, index: usize) -> bool {
+        self.bits & (1 << index) != 0
+    }
+}
+
+impl std::fmt::Debug for BitVec32 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:0b}", self.bits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bitvec_set_one() {
+        let mut bit_vec = BitVec32::new();
+        assert_eq!(bit_vec.get(0), false);
+        bit_vec.set(0, true);
+        assert_eq!(bit_vec.get(0), true);
+    }
+
+    #[test]
+    fn bitvec_set_many() {
+        let mut bit_vec = BitVec32::new();
+        bit_vec.set(0, true);
+        bit_vec.set(2, true);
+        assert_eq!(bit_vec.get(0), true);
+        assert_eq!(bit_vec.get(1), false);
+        assert_eq!(bit_vec.get(2), true);
+        assert_eq!(bit_vec.get(3), false);
+    }
+
+    const EXAMPLE: &str = "\
+Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
+Valve BB has flow rate=13; tunnels lead to valves CC, AA
+Valve CC has flow rate=2; tunnels lead to valves DD, BB
+Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE
+Valve EE has flow rate=3; tunnels lead to valves FF, DD
+Valve FF has flow rate=0; tunnels lead to valves EE, GG
+Valve GG has flow rate=0; tunnels lead to valves FF, HH
+Valve HH has flow rate=22; tunnel leads to valve GG
+Valve II has flow rate=0; tunnels lead to valves AA, JJ
+Valve JJ has flow rate=21; tunnel leads to valve II
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(day16b(EXAMPLE).unwrap(), "1707");
+    }
+
+    /// DD is three minutes from AA once XX and YY are compressed away
+    const LONG_TUNNEL: &str = "\
+Valve AA has flow rate=0; tunnels lead to valves EE, XX
+Valve EE has flow rate=10; tunnel leads to valve AA
+Valve XX has flow rate=0; tunnels lead to valves AA, YY
+Valve YY has flow rate=0; tunnels lead to valves XX, DD
+Valve DD has flow rate=20; tunnel leads to valve YY
+";
+
+    #[test]
+    fn walks_long_tunnels() {
+        let (valves, aa, _) = parse_input(LONG_TUNNEL).unwrap();
+        let mut tunnels = valves[&aa].tunnels.clone();
+        tunnels.sort_by_key(|(_, length)| *length);
+        let names = |(id, length): (ValveId, u8)| (valves[&id].name.as_str(), length);
+
+        assert_eq!(
+            tunnels.into_iter().map(names).collect_vec(),
+            [("EE", 1), ("DD", 3)]
+        );
+
+        // EE opens after 2 minutes and DD after 4, instead of both after 2
+        assert_eq!(day16b(LONG_TUNNEL).unwrap(), "680");
+    }
+}
+
+#[derive(Debug, Clone, Copy, Hash)]
+enum Action {
+    Move(ValveId, u8),
+    Open,
+    /// Keeps walking down a tunnel that takes more than a minute
+    Travel,
+}
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+struct State {
+    time: u8,
+    open_valves: BitVec32,
+    released_pressure: u16,
+
+    current_valve: ValveId,
+    helper_current_valve: ValveId,
+
+    /// Minutes left until arriving at the current valve
+    travel_time: u8,
+    helper_travel_time: u8,
+}
+
+impl State {
+    fn create_initial(current_valve: ValveId, helper_current_valve: Option<ValveId>) -> Self {
+        let open_valves = BitVec32::new();
+
+        Self {
+            time: 1,
+            released_pressure: 0,
+            helper_current_valve: helper_current_valve.unwrap_or(ValveId(0)),
+            current_valve,
+            open_valves,
+            travel_time: 0,
+            helper_travel_time: 0,
+        }
+    }
+
+    fn perform_actions(
+        &self,
+        my_action: Action,
+        helper_action: Option<Action>,
+        valves: &Valves,
+    ) -> Self {
+        let mut new_state = self.clone();
+
+        new_state.released_pressure += self.flow_rate(valves);
+
+        match my_action {
+            Action::Move(valve_id, length) => {
+                new_state.current_valve = valve_id;
+                new_state.travel_time = length - 1;
+            }
+            Action::Open => {
+                new_state
+                    .open_valves
+                    .set(self.current_valve.0 as usize, true);
+            }
+            Action::Travel => {
+                new_state.travel_time -= 1;
+            }
+        }
+
+        match helper_action {
+            None => {}
+            Some(Action::Move(valve_id, length)) => {
+                new_state.helper_current_valve = valve_id;
+                new_state.helper_travel_time = length - 1;
+            }
+            Some(Action::Open) => {
+                new_state
+                    .open_valves
+                    .set(self.helper_current_valve.0 as usize, true);
+            }
+            Some(Action::Travel) => {
+                new_state.helper_travel_time -= 1;
+            }
+        }
+
+        new_state.time += 1;
+
+        new_state
+    }
+
+    fn is_valve_open(&self, valve_id: ValveId) -> bool {
+        if valve_id.0 >= 32 {
+            return false;
+        }
+
+        self.open_valves.get(valve_id.0 as usize)
+    }
+
+    fn flow_rate(&self, valves: &Valves) -> u16 {
+        let mut flow_rate = 0;
+
+        for (id, valve) in valves {
+            if self.is_valve_open(*id) {
+                flow_rate += valve.flow_rate as u16;
+            }
+        }
+
+        flow_rate
+    }
+}
+
+struct Cave {
+    valves: Valves,
+    useful_valves_count: u8,
+    /// `(id, flow rate)` of the valves with a flow rate, the largest first
+    useful_valves: Vec<(ValveId, u8)>,
+}
+
+impl Cave {
+    fn actions(&self, state: &State, valve_id: ValveId, travel_time: u8) -> ArrayVec<Action, 6> {
+        let mut actions = ArrayVec::new();
+
+        if travel_time > 0 {
+            actions.push(Action::Travel);
+            return actions;
+        }
+
+        let valve = self.valves.get(&valve_id).unwrap();
+
+        if valve.flow_rate > 0 && !state.is_valve_open(valve_id) {
+            actions.push(Action::Open);
+        }
+
+        for (tunnel, cost) in &valve.tunnels {
+            actions.push(Action::Move(*tunnel, *cost));
+        }
+
+        actions
+    }
+}
+
+impl Problem for Cave {
+    type State = State;
+    type Score = u16;
+
+    fn leaf_score(&self, state: &State) -> Option<u16> {
+        if state.time == TOTAL_ROUNDS {
+            return Some(state.released_pressure);
+        }
+
+        if (state.open_valves.bits.count_ones() as u8) == self.useful_valves_count {
+            // Simulate to end
+            return Some(
+                state.released_pressure
+                    + (TOTAL_ROUNDS - state.time) as u16 * state.flow_rate(&self.valves),
+            );
+        }
+
+        None
+    }
+
+    /// Opens the closed valves largest first, as fast as possible: both of us open one right
+    /// away, and each one after that takes another minute to walk to.
+    fn upper_bound(&self, state: &State) -> Option<u16> {
+        let remaining_time = (TOTAL_ROUNDS - state.time) as u16;
+        let mut bound = state.released_pressure + state.flow_rate(&self.valves) * remaining_time;
+
+        let closed_valves = self
+            .useful_valves
+            .iter()
+            .filter(|(id, _)| !state.is_valve_open(*id));
+
+        for (i, (_, flow_rate)) in closed_valves.enumerate() {
+            let open_time = remaining_time.saturating_sub(1 + 2 * (i as u16 / 2));
+            bound += *flow_rate as u16 * open_time;
+        }
+
+        Some(bound)
+    }
+
+    fn branch(&self, state: &State, out: &mut Vec<State>) {
+        let my_actions = self.actions(state, state.current_valve, state.travel_time);
+        let helper_actions =
+            self.actions(state, state.helper_current_valve, state.helper_travel_time);
+
+        // Try all combinations of my actions and helper actions
+        for my_action in &my_actions {
+            for helper_action in &helper_actions {
+                // Prune some useless actions
+                let useful = match (my_action, helper_action) {
+                    (Action::Move(my_tunnel, _), Action::Move(helper_tunnel, _)) => {
+                        my_tunnel != helper_tunnel
+                    }
+                    (Action::Open, Action::Open) => {
+                        state.current_valve != state.helper_current_valve
+                    }
+                    _ => true,
+                };
+
+                if useful {
+                    out.push(state.perform_actions(*my_action, Some(*helper_action), &self.valves));
+                }
+            }
+        }
+    }
+}
+
+const TOTAL_ROUNDS: u8 = 27;
+
+pub fn day16b(input: &str) -> ParseResult<String> {
+    let (valves, initial_valve, useful_valves_count) = parse_input(input)?;
+
+    let useful_valves = valves
+        .values()
+        .filter(|valve| valve.flow_rate > 0)
+        .map(|valve| (valve.id, valve.flow_rate))
+        .sorted_by_key(|(_, flow_rate)| Reverse(*flow_rate))
+        .collect_vec();
+
+    let cave = Cave {
+        valves,
+        useful_valves_count: useful_valves_count as u8,
+        useful_valves,
+    };
+
+    let token = cancel::current();
+    let initial_state = State::create_initial(initial_valve, Some(initial_valve));
+    let incumbent = Incumbent::default();
+
+    let (tx, rx) = mpsc::channel();
+
+    let result = thread::scope(|scope| {
+        let incumbent = &incumbent;
+
+        let progress_thread = log_enabled!(Level::Debug).then(|| {
+            scope.spawn(move || {
+                while let Err(RecvTimeoutError::Timeout) = rx.recv_timeout(Duration::from_secs(1)) {
+                    debug!("Best score: {}", incumbent.get());
+                }
+            })
+        });
+
+        // No memoization: the released pressure is part of the state, so states hardly ever
+        // repeat and the cache would only grow
+        let search = BranchAndBound::new(&cave, incumbent).cancellable(token.clone());
+        let result = search.solve(initial_state);
+        debug!("Branch-and-bound: {}", search.stats());
+
+        if progress_thread.is_some() {
+            tx.send(()).unwrap();
+        }
+
+        result
+    });
+
+    if token.is_cancelled() {
+        return Ok(incumbent.get().to_string());
+    }
+
+    Ok(result.unwrap_or(0).to_string())
+}
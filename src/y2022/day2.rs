@@ -0,0 +1,857 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+
+use crate::parse::{self, ParseError, ParseResult};
+
+/// A shape of a [`Game`], the index into its shapes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Shape(pub usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RoundResult {
+    Win,
+    Draw,
+    Loss,
+}
+
+impl RoundResult {
+    /// In the order of the outcome symbols and scores.
+    pub const ALL: [RoundResult; 3] = [RoundResult::Loss, RoundResult::Draw, RoundResult::Win];
+
+    fn index(self) -> usize {
+        match self {
+            RoundResult::Loss => 0,
+            RoundResult::Draw => 1,
+            RoundResult::Win => 2,
+        }
+    }
+}
+
+const ROCK_PAPER_SCISSORS: &[&str] = &["Rock", "Paper", "Scissors"];
+
+const ROCK_PAPER_SCISSORS_RULES: &[(&str, &str)] = &[
+    ("Rock", "Scissors"),
+    ("Paper", "Rock"),
+    ("Scissors", "Paper"),
+];
+
+const ROCK_PAPER_SCISSORS_LIZARD_SPOCK: &[&str] = &["Rock", "Paper", "Scissors", "Lizard", "Spock"];
+
+const ROCK_PAPER_SCISSORS_LIZARD_SPOCK_RULES: &[(&str, &str)] = &[
+    ("Scissors", "Paper"),
+    ("Paper", "Rock"),
+    ("Rock", "Lizard"),
+    ("Lizard", "Spock"),
+    ("Spock", "Scissors"),
+    ("Scissors", "Lizard"),
+    ("Lizard", "Paper"),
+    ("Paper", "Spock"),
+    ("Spock", "Rock"),
+    ("Rock", "Scissors"),
+];
+
+/// A game where every two different shapes have a winner, as given by a rule table, and a
+/// round scores the value of the shape played plus a bonus for its outcome.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    pub shapes: Vec<String>,
+    /// `beats[a][b]` if shape `a` beats shape `b`
+    beats: Vec<Vec<bool>>,
+    pub shape_scores: Vec<u32>,
+    /// Bonus for a loss, a draw and a win
+    pub outcome_scores: [u32; 3],
+    /// Symbols of the opponent's shapes in the first column of the guide
+    pub opponent_symbols: Vec<String>,
+    /// Symbols of the second column when it gives our shape
+    pub shape_symbols: Vec<String>,
+    /// Symbols of the second column when it gives the outcome, for a loss, a draw and a win
+    pub outcome_symbols: [String; 3],
+}
+
+impl Game {
+    /// A game of the given shapes, worth 1, 2, 3, ... points, with a rule table of
+    /// `(winner, loser)` pairs. The opponent's shapes are written A, B, C, ... and ours the
+    /// same number of letters up to Z. The outcomes are X, Y and Z.
+    pub fn new(shapes: &[&str], rules: &[(&str, &str)]) -> ParseResult<Self> {
+        let index = |name: &str| {
+            shapes
+                .iter()
+                .position(|shape| *shape == name)
+                .ok_or_else(|| ParseError::new(format!("unknown shape `{}` in the rules", name)))
+        };
+
+        if shapes.is_empty() {
+            return Err(ParseError::new("a game needs at least one shape"));
+        }
+
+        if rules.is_empty() {
+            return Err(ParseError::new("a game needs at least one rule"));
+        }
+
+        if shapes.len() > 26 {
+            return Err(ParseError::new("at most 26 shapes are supported"));
+        }
+
+        let mut beats = vec![vec![false; shapes.len()]; shapes.len()];
+
+        for (winner, loser) in rules {
+            let (winner, loser) = (index(winner)?, index(loser)?);
+
+            if winner == loser {
+                return Err(ParseError::new(format!(
+                    "{} can't beat itself",
+                    shapes[winner]
+                )));
+            }
+
+            beats[winner][loser] = true;
+        }
+
+        for a in 0..shapes.len() {
+            for b in a + 1..shapes.len() {
+                if beats[a][b] == beats[b][a] {
+                    return Err(ParseError::new(format!(
+                        "the rules need exactly one winner between {} and {}",
+                        shapes[a], shapes[b]
+                    )));
+                }
+            }
+        }
+
+        let letters = |first: u8| {
+            (first..first + shapes.len() as u8)
+                .map(|letter| (letter as char).to_string())
+                .collect()
+        };
+
+        Ok(Self {
+            shapes: shapes.iter().map(|shape| shape.to_string()).collect(),
+            beats,
+            shape_scores: (1..=shapes.len() as u32).collect(),
+            outcome_scores: [0, 3, 6],
+            opponent_symbols: letters(b'A'),
+            shape_symbols: letters(b'Z' + 1 - shapes.len() as u8),
+            outcome_symbols: ["X", "Y", "Z"].map(String::from),
+        })
+    }
+
+    /// The game of the puzzle.
+    pub fn rock_paper_scissors() -> Self {
+        Self::new(ROCK_PAPER_SCISSORS, ROCK_PAPER_SCISSORS_RULES).unwrap()
+    }
+
+    /// Rock, paper, scissors, lizard, Spock, written A to E and V to Z in the guide.
+    pub fn rock_paper_scissors_lizard_spock() -> Self {
+        Self::new(
+            ROCK_PAPER_SCISSORS_LIZARD_SPOCK,
+            ROCK_PAPER_SCISSORS_LIZARD_SPOCK_RULES,
+        )
+        .unwrap()
+    }
+
+    pub fn name(&self, shape: Shape) -> &str {
+        &self.shapes[shape.0]
+    }
+
+    /// The outcome of playing `mine` against `other`.
+    pub fn play_round(&self, mine: Shape, other: Shape) -> RoundResult {
+        if self.beats[mine.0][other.0] {
+            RoundResult::Win
+        } else if self.beats[other.0][mine.0] {
+            RoundResult::Loss
+        } else {
+            RoundResult::Draw
+        }
+    }
+
+    pub fn outcome_score(&self, result: RoundResult) -> u32 {
+        self.outcome_scores[result.index()]
+    }
+
+    /// Our score for a round of `mine` against `other`.
+    pub fn score(&self, mine: Shape, other: Shape) -> u32 {
+        self.shape_scores[mine.0] + self.outcome_score(self.play_round(mine, other))
+    }
+
+    pub fn all_shapes(&self) -> impl Iterator<Item = Shape> {
+        (0..self.shapes.len()).map(Shape)
+    }
+
+    /// The shape to play against `other` for the result. Of several such shapes the one worth
+    /// the most, `None` if there is none.
+    pub fn shape_for(&self, other: Shape, result: RoundResult) -> Option<Shape> {
+        self.all_shapes()
+            .filter(|&mine| self.play_round(mine, other) == result)
+            .max_by_key(|mine| self.shape_scores[mine.0])
+    }
+
+    /// Like [`Game::shape_for`], but a missing shape is an error in the guide.
+    fn required_shape(&self, other: Shape, result: RoundResult) -> ParseResult<Shape> {
+        self.shape_for(other, result).ok_or_else(|| {
+            ParseError::new(format!(
+                "no shape gives a {:?} against {}",
+                result,
+                self.name(other)
+            ))
+        })
+    }
+
+    fn symbol<T: Copy>(symbols: &[String], values: &[T], s: &str, what: &str) -> ParseResult<T> {
+        symbols
+            .iter()
+            .position(|symbol| symbol == s)
+            .and_then(|i| values.get(i).copied())
+            .ok_or_else(|| ParseError::new(format!("unknown {} `{}`", what, s)))
+    }
+
+    fn parse_lines<T>(
+        &self,
+        input: &str,
+        mut second: impl FnMut(&str) -> ParseResult<T>,
+    ) -> ParseResult<Vec<(Shape, T)>> {
+        let shapes = self.all_shapes().collect::<Vec<_>>();
+
+        parse::parse_lines(input, |line| {
+            let (opponent, mine) = parse::split_once(line, " ")?;
+            let opponent = Self::symbol(&self.opponent_symbols, &shapes, opponent, "shape")?;
+            Ok((opponent, second(mine)?))
+        })
+    }
+
+    /// Reads the guide as the opponent's shape and the index of the second column in `symbols`.
+    fn parse_symbols(&self, input: &str, symbols: &[String]) -> ParseResult<Vec<(Shape, usize)>> {
+        let indices = (0..symbols.len()).collect::<Vec<_>>();
+        self.parse_lines(input, |s| Self::symbol(symbols, &indices, s, "symbol"))
+    }
+
+    /// Reads the guide as the opponent's shape and ours.
+    pub fn parse_shapes(&self, input: &str) -> ParseResult<Vec<(Shape, Shape)>> {
+        let shapes = self.all_shapes().collect::<Vec<_>>();
+        self.parse_lines(input, |s| {
+            Self::symbol(&self.shape_symbols, &shapes, s, "shape")
+        })
+    }
+
+    /// Reads the guide as the opponent's shape and the result to aim for.
+    pub fn parse_outcomes(&self, input: &str) -> ParseResult<Vec<(Shape, RoundResult)>> {
+        self.parse_lines(input, |s| {
+            Self::symbol(&self.outcome_symbols, &RoundResult::ALL, s, "round result")
+        })
+    }
+}
+
+/// The guide's total when the second column is our shape.
+fn shape_total(game: &Game, input: &str) -> ParseResult<u64> {
+    let rounds = game.parse_shapes(input)?;
+    Ok(rounds
+        .into_iter()
+        .map(|(opponent, mine)| game.score(mine, opponent) as u64)
+        .sum())
+}
+
+/// The guide's total when the second column is the result to aim for.
+fn outcome_total(game: &Game, input: &str) -> ParseResult<u64> {
+    let mut total = 0;
+
+    for (opponent, result) in game.parse_outcomes(input)? {
+        total += game.score(game.required_shape(opponent, result)?, opponent) as u64;
+    }
+
+    Ok(total)
+}
+
+/// Predicts the opponent's next shape from the shapes that followed the same last `order`
+/// shapes before, backing off to shorter histories when the last ones haven't come up yet.
+/// Order 0 is plain frequency counting.
+#[derive(Debug, Clone)]
+pub struct Markov {
+    order: usize,
+    shapes: usize,
+    /// How often each shape followed each history of up to `order` shapes
+    counts: HashMap<Vec<Shape>, Vec<u64>>,
+}
+
+impl Markov {
+    pub fn new(order: usize, game: &Game) -> Self {
+        Self {
+            order,
+            shapes: game.shapes.len(),
+            counts: HashMap::new(),
+        }
+    }
+
+    /// The probability of each shape coming next after `history`, uniform if nothing is known.
+    pub fn predict(&self, history: &[Shape]) -> Vec<f64> {
+        for length in (0..=self.order.min(history.len())).rev() {
+            let context = &history[history.len() - length..];
+
+            if let Some(counts) = self.counts.get(context) {
+                let total = counts.iter().sum::<u64>() as f64;
+                return counts.iter().map(|&count| count as f64 / total).collect();
+            }
+        }
+
+        vec![1.0 / self.shapes as f64; self.shapes]
+    }
+
+    pub fn observe(&mut self, history: &[Shape], next: Shape) {
+        for length in 0..=self.order.min(history.len()) {
+            let context = history[history.len() - length..].to_vec();
+            let counts = self
+                .counts
+                .entry(context)
+                .or_insert_with(|| vec![0; self.shapes]);
+            counts[next.0] += 1;
+        }
+    }
+}
+
+/// The shape with the best expected score against the predicted shapes.
+pub fn best_response(game: &Game, prediction: &[f64]) -> Shape {
+    let expected_score = |mine: Shape| {
+        game.all_shapes()
+            .map(|other| prediction[other.0] * game.score(mine, other) as f64)
+            .sum::<f64>()
+    };
+
+    game.all_shapes()
+        .max_by(|&a, &b| expected_score(a).total_cmp(&expected_score(b)))
+        .unwrap_or(Shape(0))
+}
+
+/// Playing the best response to the opponent model, compared with following the guide.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CounterPlay {
+    pub order: usize,
+    pub rounds: usize,
+    /// Rounds where the most likely shape was the one the opponent played
+    pub predicted: usize,
+    pub total: u64,
+    /// The guide's total with the second column as our shape
+    pub shape_total: u64,
+    /// The guide's total with the second column as the result to aim for
+    pub outcome_total: u64,
+}
+
+impl CounterPlay {
+    pub fn uplift_over_shapes(&self) -> i64 {
+        self.total as i64 - self.shape_total as i64
+    }
+
+    pub fn uplift_over_outcomes(&self) -> i64 {
+        self.total as i64 - self.outcome_total as i64
+    }
+}
+
+/// Plays the guide's rounds against a Markov model of the given order that only learns from
+/// the rounds played so far.
+pub fn counter_play(game: &Game, input: &str, order: usize) -> ParseResult<CounterPlay> {
+    let opponent = game
+        .parse_shapes(input)?
+        .into_iter()
+        .map(|(opponent, _)| opponent)
+        .collect::<Vec<_>>();
+    let mut model = Markov::new(order, game);
+    let (mut predicted, mut total) = (0, 0);
+
+    for (round, &other) in opponent.iter().enumerate() {
+        let history = &opponent[..round];
+        let prediction = model.predict(history);
+
+        let most_likely = game
+            .all_shapes()
+            .max_by(|a, b| prediction[a.0].total_cmp(&prediction[b.0]));
+        predicted += (most_likely == Some(other)) as usize;
+        total += game.score(best_response(game, &prediction), other) as u64;
+
+        model.observe(history, other);
+    }
+
+    Ok(CounterPlay {
+        order,
+        rounds: opponent.len(),
+        predicted,
+        total,
+        shape_total: shape_total(game, input)?,
+        outcome_total: outcome_total(game, input)?,
+    })
+}
+
+/// Mixed strategies that neither player can improve on alone.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Equilibrium {
+    /// Probability of playing each shape
+    pub mine: Vec<f64>,
+    pub other: Vec<f64>,
+    /// How much more we expect to score than the opponent per round
+    pub value: f64,
+}
+
+/// Below this, numbers are taken as 0 in the simplex method.
+const EPSILON: f64 = 1e-9;
+
+/// How much more we score than the opponent for each of our shapes against each of theirs,
+/// `payoff[mine][other]`. What one player gains the other loses, which makes it a zero-sum
+/// game.
+pub fn payoff_matrix(game: &Game) -> Vec<Vec<f64>> {
+    game.all_shapes()
+        .map(|mine| {
+            game.all_shapes()
+                .map(|other| game.score(mine, other) as f64 - game.score(other, mine) as f64)
+                .collect()
+        })
+        .collect()
+}
+
+/// Solves the zero-sum game where we maximize and the opponent minimizes the payoff, as the
+/// linear program max Σy subject to Ay <= 1, y >= 0 for the payoff shifted to be positive.
+/// Its solution gives the opponent's strategy and its dual ours.
+pub fn solve_zero_sum(payoff: &[Vec<f64>]) -> Equilibrium {
+    let (rows, columns) = (payoff.len(), payoff[0].len());
+    let shift = 1.0
+        - payoff
+            .iter()
+            .flatten()
+            .copied()
+            .fold(f64::INFINITY, f64::min);
+
+    // Constraints over the columns and a slack per row, then the right hand side, and the
+    // objective row last. `basis[i]` is the variable constraint `i` solves for.
+    let width = columns + rows + 1;
+    let mut tableau = vec![vec![0.0; width]; rows + 1];
+    let mut basis = (columns..columns + rows).collect::<Vec<_>>();
+
+    for (i, row) in payoff.iter().enumerate() {
+        for (j, payoff) in row.iter().enumerate() {
+            tableau[i][j] = payoff + shift;
+        }
+        tableau[i][columns + i] = 1.0;
+        tableau[i][width - 1] = 1.0;
+    }
+
+    tableau[rows][..columns].fill(-1.0);
+
+    // Bland's rule: the first improving variable enters, which can't cycle
+    while let Some(entering) = (0..width - 1).find(|&j| tableau[rows][j] < -EPSILON) {
+        let Some(leaving) = (0..rows)
+            .filter(|&i| tableau[i][entering] > EPSILON)
+            .min_by(|&a, &b| {
+                let ratio = |i: usize| tableau[i][width - 1] / tableau[i][entering];
+                ratio(a).total_cmp(&ratio(b)).then(basis[a].cmp(&basis[b]))
+            })
+        else {
+            // Can't happen with a positive payoff, the program is bounded
+            break;
+        };
+
+        let pivot = tableau[leaving][entering];
+        tableau[leaving]
+            .iter_mut()
+            .for_each(|value| *value /= pivot);
+
+        let pivot_row = tableau[leaving].clone();
+
+        for (i, row) in tableau.iter_mut().enumerate() {
+            let factor = row[entering];
+
+            if i != leaving && factor != 0.0 {
+                for (value, pivot) in row.iter_mut().zip(&pivot_row) {
+                    *value -= factor * pivot;
+                }
+            }
+        }
+
+        basis[leaving] = entering;
+    }
+
+    let objective = tableau[rows][width - 1];
+    let value = 1.0 / objective;
+
+    let mut other = vec![0.0; columns];
+    for (i, &variable) in basis.iter().enumerate() {
+        if variable < columns {
+            other[variable] = tableau[i][width - 1] * value;
+        }
+    }
+
+    let mine = (0..rows)
+        .map(|i| tableau[rows][columns + i] * value)
+        .collect();
+
+    Equilibrium {
+        mine,
+        other,
+        value: value - shift,
+    }
+}
+
+pub fn equilibrium(game: &Game) -> Equilibrium {
+    solve_zero_sum(&payoff_matrix(game))
+}
+
+/// The guide's total under one reading of the second column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Interpretation {
+    /// What each symbol stands for, e.g. `X=Rock Y=Paper Z=Scissors`
+    pub mapping: String,
+    pub total: u64,
+}
+
+/// How much the guide's total depends on what the second column means.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sensitivity {
+    /// Every one-to-one reading of the symbols, best first
+    pub interpretations: Vec<Interpretation>,
+    /// The mean total if every reading is equally likely
+    pub expected: f64,
+}
+
+impl Sensitivity {
+    fn new(mut interpretations: Vec<Interpretation>) -> Self {
+        interpretations.sort_by_key(|interpretation| std::cmp::Reverse(interpretation.total));
+        let sum = interpretations
+            .iter()
+            .map(|interpretation| interpretation.total)
+            .sum::<u64>();
+
+        Self {
+            expected: sum as f64 / interpretations.len() as f64,
+            interpretations,
+        }
+    }
+
+    pub fn best(&self) -> &Interpretation {
+        &self.interpretations[0]
+    }
+
+    pub fn worst(&self) -> &Interpretation {
+        &self.interpretations[self.interpretations.len() - 1]
+    }
+}
+
+/// How often each symbol of the second column comes up against each of the opponent's shapes.
+fn count_rounds(game: &Game, input: &str, symbols: &[String]) -> ParseResult<Vec<Vec<u64>>> {
+    let mut counts = vec![vec![0; symbols.len()]; game.shapes.len()];
+
+    for (opponent, symbol) in game.parse_symbols(input, symbols)? {
+        counts[opponent.0][symbol] += 1;
+    }
+
+    Ok(counts)
+}
+
+/// Scores the guide with every symbol of the second column standing for a different value,
+/// where `scores[opponent][value]` is the score of a round.
+fn interpret<T: Copy>(
+    symbols: &[String],
+    values: &[T],
+    counts: &[Vec<u64>],
+    scores: &[Vec<u32>],
+    name: impl Fn(T) -> String,
+) -> Sensitivity {
+    let interpretations = (0..values.len())
+        .permutations(values.len())
+        .map(|meaning| {
+            let total = counts
+                .iter()
+                .zip(scores)
+                .map(|(counts, scores)| {
+                    (counts.iter().zip(&meaning))
+                        .map(|(count, &value)| count * scores[value] as u64)
+                        .sum::<u64>()
+                })
+                .sum();
+            let mapping = symbols
+                .iter()
+                .zip(&meaning)
+                .map(|(symbol, &value)| format!("{}={}", symbol, name(values[value])))
+                .join(" ");
+
+            Interpretation { mapping, total }
+        })
+        .collect();
+
+    Sensitivity::new(interpretations)
+}
+
+/// Reads the second column as our shape, in every possible way.
+pub fn shape_interpretations(game: &Game, input: &str) -> ParseResult<Sensitivity> {
+    let counts = count_rounds(game, input, &game.shape_symbols)?;
+    let shapes = game.all_shapes().collect::<Vec<_>>();
+    let scores = shapes
+        .iter()
+        .map(|&opponent| {
+            shapes
+                .iter()
+                .map(|&mine| game.score(mine, opponent))
+                .collect()
+        })
+        .collect::<Vec<_>>();
+
+    Ok(interpret(
+        &game.shape_symbols,
+        &shapes,
+        &counts,
+        &scores,
+        |shape| game.name(shape).to_string(),
+    ))
+}
+
+/// Reads the second column as the result to aim for, in every possible way.
+pub fn outcome_interpretations(game: &Game, input: &str) -> ParseResult<Sensitivity> {
+    let counts = count_rounds(game, input, &game.outcome_symbols)?;
+    let mut scores = Vec::new();
+
+    for opponent in game.all_shapes() {
+        let mut row = Vec::new();
+
+        for result in RoundResult::ALL {
+            let mine = game.required_shape(opponent, result)?;
+            row.push(game.score(mine, opponent));
+        }
+
+        scores.push(row);
+    }
+
+    Ok(interpret(
+        &game.outcome_symbols,
+        &RoundResult::ALL,
+        &counts,
+        &scores,
+        |result| format!("{:?}", result),
+    ))
+}
+
+pub fn explain(input: &str) -> ParseResult<String> {
+    let game = Game::rock_paper_scissors();
+    let mut lines = Vec::new();
+
+    for (title, sensitivity) in [
+        ("shapes", shape_interpretations(&game, input)?),
+        ("outcomes", outcome_interpretations(&game, input)?),
+    ] {
+        if !lines.is_empty() {
+            lines.push(String::new());
+        }
+
+        lines.push(format!("Second column as {}:", title));
+
+        for interpretation in &sensitivity.interpretations {
+            lines.push(format!(
+                "{:<32}{}",
+                interpretation.mapping, interpretation.total
+            ));
+        }
+
+        lines.push(format!(
+            "best {}, worst {}, expected {:.1}",
+            sensitivity.best().total,
+            sensitivity.worst().total,
+            sensitivity.expected
+        ));
+    }
+
+    let equilibrium = equilibrium(&game);
+    lines.push(String::new());
+    lines.push(format!(
+        "Equilibrium, {:+.3} points per round against a perfect opponent:",
+        equilibrium.value
+    ));
+
+    for shape in game.all_shapes() {
+        lines.push(format!(
+            "{:<10}{:.3} (opponent {:.3})",
+            game.name(shape),
+            equilibrium.mine[shape.0],
+            equilibrium.other[shape.0]
+        ));
+    }
+
+    lines.push(String::new());
+    lines.push("Best response to an opponent model:".to_string());
+
+    for order in 0..=3 {
+        let play = counter_play(&game, input, order)?;
+
+        lines.push(format!(
+            "order {}: {} ({:+} over shapes, {:+} over outcomes), predicted {} of {} rounds",
+            order,
+            play.total,
+            play.uplift_over_shapes(),
+            play.uplift_over_outcomes(),
+            play.predicted,
+            play.rounds
+        ));
+    }
+
+    Ok(lines.join("\n"))
+}
+
+/// Entry point for fuzzing the parser.
+pub fn fuzz(input: &str) -> ParseResult<()> {
+    let game = Game::rock_paper_scissors();
+    game.parse_shapes(input)?;
+    game.parse_outcomes(input).map(drop)
+}
+
+pub fn day2a(input: &str) -> ParseResult<String> {
+    Ok(shape_total(&Game::rock_paper_scissors(), input)?.to_string())
+}
+
+pub fn day2b(input: &str) -> ParseResult<String> {
+    Ok(outcome_total(&Game::rock_paper_scissors(), input)?.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "A Y\nB X\nC Z\n";
+
+    #[test]
+    fn plays_both_interpretations() {
+        assert_eq!(day2a(EXAMPLE).unwrap(), "15");
+        assert_eq!(day2b(EXAMPLE).unwrap(), "12");
+    }
+
+    #[test]
+    fn scores_every_interpretation() {
+        let game = Game::rock_paper_scissors();
+
+        let shapes = shape_interpretations(&game, EXAMPLE).unwrap();
+        assert_eq!(shapes.interpretations.len(), 6);
+        assert_eq!(shapes.best().total, 24);
+        assert_eq!(shapes.best().mapping, "X=Scissors Y=Paper Z=Rock");
+        assert_eq!(shapes.worst().total, 6);
+        assert_eq!(shapes.expected, 15.0);
+
+        let puzzle = shapes
+            .interpretations
+            .iter()
+            .find(|interpretation| interpretation.mapping == "X=Rock Y=Paper Z=Scissors");
+        assert_eq!(puzzle.unwrap().total, 15);
+
+        let outcomes = outcome_interpretations(&game, EXAMPLE).unwrap();
+        assert_eq!(outcomes.expected, 15.0);
+        let puzzle = outcomes
+            .interpretations
+            .iter()
+            .find(|interpretation| interpretation.mapping == "X=Loss Y=Draw Z=Win");
+        assert_eq!(puzzle.unwrap().total, 12);
+
+        let spock = Game::rock_paper_scissors_lizard_spock();
+        assert_eq!(
+            shape_interpretations(&spock, "A V\n")
+                .unwrap()
+                .interpretations
+                .len(),
+            120
+        );
+    }
+
+    #[test]
+    fn counters_a_predictable_opponent() {
+        let guide = "A X\nB X\nC X\n".repeat(4);
+        let game = Game::rock_paper_scissors();
+
+        let play = counter_play(&game, &guide, 1).unwrap();
+        assert_eq!((play.rounds, play.predicted), (12, 8));
+        assert_eq!(play.total, 3 + 5 + 2 + 3 + 8 * 8);
+        assert_eq!(play.shape_total, 48);
+        assert_eq!(play.uplift_over_shapes(), 29);
+
+        // Without history every shape is as likely, so only the shape values count
+        let frequencies = counter_play(&game, &guide, 0).unwrap();
+        assert_eq!(best_response(&game, &[1.0 / 3.0; 3]), Shape(2));
+        assert!(frequencies.total < play.total);
+    }
+
+    /// Checks that neither player gains by switching to any single shape.
+    fn assert_equilibrium(game: &Game) -> Equilibrium {
+        let payoff = payoff_matrix(game);
+        let equilibrium = solve_zero_sum(&payoff);
+        let n = payoff.len();
+
+        assert!((equilibrium.mine.iter().sum::<f64>() - 1.0).abs() < 1e-6);
+        assert!((equilibrium.other.iter().sum::<f64>() - 1.0).abs() < 1e-6);
+
+        for (shape, row) in payoff.iter().enumerate() {
+            let against = (0..n).map(|mine| equilibrium.mine[mine] * payoff[mine][shape]);
+            assert!(against.sum::<f64>() >= equilibrium.value - 1e-6);
+
+            let playing = (0..n).map(|other| equilibrium.other[other] * row[other]);
+            assert!(playing.sum::<f64>() <= equilibrium.value + 1e-6);
+        }
+
+        equilibrium
+    }
+
+    #[test]
+    fn finds_mixed_equilibria() {
+        let equilibrium = assert_equilibrium(&Game::rock_paper_scissors());
+        let expected = [7.0 / 18.0, 4.0 / 18.0, 7.0 / 18.0];
+        for (p, expected) in equilibrium.mine.iter().zip(expected) {
+            assert!((p - expected).abs() < 1e-6);
+        }
+
+        let mut fair = Game::rock_paper_scissors();
+        fair.shape_scores = vec![0, 0, 0];
+        let equilibrium = assert_equilibrium(&fair);
+        assert!(equilibrium.value.abs() < 1e-6);
+        assert!(equilibrium
+            .mine
+            .iter()
+            .all(|&p| (p - 1.0 / 3.0).abs() < 1e-6));
+
+        assert_equilibrium(&Game::rock_paper_scissors_lizard_spock());
+    }
+
+    #[test]
+    fn plays_rock_paper_scissors_lizard_spock() {
+        let game = Game::rock_paper_scissors_lizard_spock();
+        let [rock, paper, scissors, lizard, spock] = [0, 1, 2, 3, 4].map(Shape);
+
+        assert_eq!(game.play_round(spock, rock), RoundResult::Win);
+        assert_eq!(game.play_round(lizard, scissors), RoundResult::Loss);
+        assert_eq!(game.play_round(paper, paper), RoundResult::Draw);
+        assert_eq!(game.score(lizard, spock), 4 + 6);
+        assert_eq!(game.shape_for(rock, RoundResult::Win), Some(spock));
+
+        let rounds = game.parse_shapes("E V\nA Z\n").unwrap();
+        assert_eq!(rounds, vec![(spock, rock), (rock, spock)]);
+
+        for shape in game.all_shapes() {
+            let wins = game
+                .all_shapes()
+                .filter(|&other| game.play_round(shape, other) == RoundResult::Win);
+            assert_eq!(wins.count(), 2);
+        }
+    }
+
+    #[test]
+    fn configures_symbols_and_scores() {
+        let mut game = Game::rock_paper_scissors();
+        game.opponent_symbols = vec!["R".into(), "P".into(), "S".into()];
+        game.shape_symbols = game.opponent_symbols.clone();
+        game.shape_scores = vec![0, 0, 0];
+        game.outcome_scores = [0, 1, 2];
+
+        let rounds = game.parse_shapes("R P\nS P\n").unwrap();
+        let total = rounds
+            .iter()
+            .map(|&(opponent, mine)| game.score(mine, opponent))
+            .sum::<u32>();
+        assert_eq!(total, 2);
+        assert!(game.parse_shapes("A X").is_err());
+    }
+
+    #[test]
+    fn rejects_incomplete_rules() {
+        assert!(Game::new(&["Rock", "Paper"], &[]).is_err());
+        assert!(Game::new(&["Rock", "Paper"], &[("Rock", "Paper"), ("Paper", "Rock")]).is_err());
+        assert!(Game::new(&["Rock"], &[("Rock", "Stone")]).is_err());
+        assert!(Game::new(&[], &[]).is_err());
+        assert!(Game::new(&["Rock"], &[]).is_err());
+    }
+}
@@ -0,0 +1,500 @@
+use crate::parse::{self, ParseError, ParseResult};
+
+/// The priority of an item: a to z are 1 to 26, A to Z 27 to 52.
+fn priority(item: char) -> Option<u32> {
+    match item {
+        'a'..='z' => Some(item as u32 - 'a' as u32 + 1),
+        'A'..='Z' => Some(item as u32 - 'A' as u32 + 27),
+        _ => None,
+    }
+}
+
+fn item(priority: u32) -> char {
+    match priority {
+        1..=26 => (b'a' + priority as u8 - 1) as char,
+        _ => (b'A' + priority as u8 - 27) as char,
+    }
+}
+
+/// A set of item types, bit `priority - 1` for each.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Items(u64);
+
+impl Items {
+    pub const ALL: Items = Items((1 << 52) - 1);
+
+    pub fn parse(items: &str) -> ParseResult<Self> {
+        items.chars().try_fold(Items::default(), |set, ch| {
+            let priority =
+                priority(ch).ok_or_else(|| ParseError::new(format!("invalid item `{}`", ch)))?;
+            Ok(Items(set.0 | 1 << (priority - 1)))
+        })
+    }
+
+    pub fn intersection(self, other: Items) -> Items {
+        Items(self.0 & other.0)
+    }
+
+    pub fn union(self, other: Items) -> Items {
+        Items(self.0 | other.0)
+    }
+
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn priorities(self) -> impl Iterator<Item = u32> {
+        (1..=52).filter(move |priority| self.0 & 1 << (priority - 1) != 0)
+    }
+
+    pub fn items(self) -> impl Iterator<Item = char> {
+        self.priorities().map(item)
+    }
+
+    /// The priority of the only item in the set, if there is exactly one.
+    pub fn single_priority(self) -> Option<u32> {
+        (self.len() == 1).then(|| self.0.trailing_zeros() + 1)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rucksack {
+    pub left: Items,
+    pub right: Items,
+}
+
+impl Rucksack {
+    /// The item types in both compartments.
+    pub fn shared(self) -> Items {
+        self.left.intersection(self.right)
+    }
+
+    pub fn items(self) -> Items {
+        self.left.union(self.right)
+    }
+}
+
+/// The item types carried by every elf of the group.
+pub fn badges(group: &[Rucksack]) -> Items {
+    group.iter().fold(Items::ALL, |common, rucksack| {
+        common.intersection(rucksack.items())
+    })
+}
+
+fn read_input(input: &str) -> ParseResult<Vec<Rucksack>> {
+    parse::parse_lines(input, |line| {
+        // Checks the items first, as only ASCII lines can be split in the middle
+        Items::parse(line)?;
+
+        if line.len() % 2 != 0 {
+            return Err(ParseError::new("odd number of items"));
+        }
+
+        let (left, right) = line.split_at(line.len() / 2);
+        Ok(Rucksack {
+            left: Items::parse(left)?,
+            right: Items::parse(right)?,
+        })
+    })
+}
+
+/// Entry point for fuzzing the parser.
+pub fn fuzz(input: &str) -> ParseResult<()> {
+    read_input(input).map(drop)
+}
+
+fn expect_single(items: Items, what: &str) -> ParseResult<u32> {
+    items.single_priority().ok_or_else(|| {
+        let found = items.items().collect::<String>();
+        ParseError::new(format!("expected one {}, found `{}`", what, found))
+    })
+}
+
+/// The sum of the badges of consecutive groups of `group_size` elves.
+pub fn badge_sum(rucksacks: &[Rucksack], group_size: usize) -> ParseResult<u32> {
+    if group_size == 0 || !rucksacks.len().is_multiple_of(group_size) {
+        return Err(ParseError::new(format!(
+            "{} rucksacks don't make groups of {}",
+            rucksacks.len(),
+            group_size
+        )));
+    }
+
+    rucksacks
+        .chunks(group_size)
+        .enumerate()
+        .map(|(i, group)| {
+            expect_single(badges(group), "badge").map_err(|error| error.at_line(i * group_size + 1))
+        })
+        .sum()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IssueKind {
+    /// The characters that aren't items
+    InvalidItems(String),
+    /// The number of items, which can't be split into two equal compartments
+    OddLength(usize),
+    /// The items in both compartments, when not exactly one
+    SharedItems(Items),
+    /// The items the group has in common, when not exactly one
+    Badges { size: usize, badges: Items },
+    /// The number of rucksacks in the last group, when it isn't full
+    IncompleteGroup(usize),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Issue {
+    /// 1-based line of the rucksack, or of the first rucksack of the group
+    pub line: usize,
+    pub kind: IssueKind,
+}
+
+/// Everything wrong with a list of rucksacks, where the solutions stop at the first problem.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Validation {
+    pub rucksacks: usize,
+    pub group_size: usize,
+    pub issues: Vec<Issue>,
+}
+
+impl Validation {
+    pub fn passed(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    pub fn render(&self) -> String {
+        let mut lines = self
+            .issues
+            .iter()
+            .map(|issue| {
+                let found = |items: Items| match items.len() {
+                    0 => "none".to_string(),
+                    _ => format!("`{}`", items.items().collect::<String>()),
+                };
+
+                match &issue.kind {
+                    IssueKind::InvalidItems(invalid) => {
+                        format!("line {}: invalid items `{}`", issue.line, invalid)
+                    }
+                    IssueKind::OddLength(length) => {
+                        format!("line {}: odd number of items ({})", issue.line, length)
+                    }
+                    IssueKind::SharedItems(shared) => format!(
+                        "line {}: items in both compartments: {}",
+                        issue.line,
+                        found(*shared)
+                    ),
+                    IssueKind::Badges { size, badges } => format!(
+                        "lines {}-{}: badges: {}",
+                        issue.line,
+                        issue.line + size - 1,
+                        found(*badges)
+                    ),
+                    IssueKind::IncompleteGroup(size) => format!(
+                        "line {}: last group has {} of {} rucksacks",
+                        issue.line, size, self.group_size
+                    ),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        lines.push(format!(
+            "{} rucksacks, {} issues: {}",
+            self.rucksacks,
+            self.issues.len(),
+            if self.passed() { "PASS" } else { "FAIL" }
+        ));
+
+        lines.join("\n")
+    }
+}
+
+/// Checks every rucksack and every group of `group_size` consecutive rucksacks. Badges are
+/// looked for among the valid items, even of rucksacks with other issues.
+pub fn validate(input: &str, group_size: usize) -> ParseResult<Validation> {
+    if group_size == 0 {
+        return Err(ParseError::new("groups need at least one rucksack"));
+    }
+
+    let mut issues = Vec::new();
+    let mut items = Vec::new();
+
+    for (i, line) in input.lines().enumerate() {
+        let issue = |kind| Issue { line: i + 1, kind };
+        let invalid = line
+            .chars()
+            .filter(|&ch| priority(ch).is_none())
+            .collect::<String>();
+        let valid = line
+            .chars()
+            .filter(|&ch| priority(ch).is_some())
+            .collect::<String>();
+
+        items.push(Items::parse(&valid).unwrap_or_default());
+
+        if !invalid.is_empty() {
+            issues.push(issue(IssueKind::InvalidItems(invalid)));
+        } else if line.len() % 2 != 0 {
+            issues.push(issue(IssueKind::OddLength(line.len())));
+        } else {
+            let (left, right) = line.split_at(line.len() / 2);
+            let shared = Items::parse(left)
+                .unwrap_or_default()
+                .intersection(Items::parse(right).unwrap_or_default());
+
+            if shared.len() != 1 {
+                issues.push(issue(IssueKind::SharedItems(shared)));
+            }
+        }
+    }
+
+    for (i, group) in items.chunks(group_size).enumerate() {
+        let line = i * group_size + 1;
+
+        if group.len() < group_size {
+            issues.push(Issue {
+                line,
+                kind: IssueKind::IncompleteGroup(group.len()),
+            });
+            continue;
+        }
+
+        let badges = group
+            .iter()
+            .fold(Items::ALL, |common, items| common.intersection(*items));
+
+        if badges.len() != 1 {
+            issues.push(Issue {
+                line,
+                kind: IssueKind::Badges {
+                    size: group_size,
+                    badges,
+                },
+            });
+        }
+    }
+
+    issues.sort_by_key(|issue| issue.line);
+
+    Ok(Validation {
+        rucksacks: items.len(),
+        group_size,
+        issues,
+    })
+}
+
+/// Groups of rucksacks that each share exactly one item, found without knowing the order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Regrouping {
+    /// Indices of the rucksacks of each group, ascending, in the order of their first rucksack
+    pub groups: Vec<Vec<usize>>,
+    /// Whether no other grouping works
+    pub unique: bool,
+}
+
+/// Backtracking over the groups, where each group is built up from its first unassigned
+/// rucksack. The items a partial group has in common only shrink, so a partial group with
+/// none left is dropped.
+struct Regrouper {
+    items: Vec<Items>,
+    group_size: usize,
+    assigned: Vec<bool>,
+    groups: Vec<Vec<usize>>,
+    /// Stops after two, which is enough to tell that the grouping isn't unique
+    found: Vec<Vec<Vec<usize>>>,
+}
+
+impl Regrouper {
+    fn place_next_group(&mut self) {
+        let Some(first) = self.assigned.iter().position(|assigned| !assigned) else {
+            self.found.push(self.groups.clone());
+            return;
+        };
+
+        self.assigned[first] = true;
+        self.extend(&mut vec![first], self.items[first], first + 1);
+        self.assigned[first] = false;
+    }
+
+    fn extend(&mut self, group: &mut Vec<usize>, common: Items, from: usize) {
+        if group.len() == self.group_size {
+            if common.len() == 1 {
+                self.groups.push(group.clone());
+                self.place_next_group();
+                self.groups.pop();
+            }
+            return;
+        }
+
+        for next in from..self.items.len() {
+            if self.found.len() >= 2 {
+                return;
+            }
+
+            let common = common.intersection(self.items[next]);
+
+            if self.assigned[next] || common.is_empty() {
+                continue;
+            }
+
+            self.assigned[next] = true;
+            group.push(next);
+            self.extend(group, common, next + 1);
+            group.pop();
+            self.assigned[next] = false;
+        }
+    }
+}
+
+/// Splits rucksacks in any order into groups of `group_size` that share exactly one item.
+/// `None` if there is no such split.
+pub fn regroup(rucksacks: &[Rucksack], group_size: usize) -> Option<Regrouping> {
+    if group_size == 0 || !rucksacks.len().is_multiple_of(group_size) {
+        return None;
+    }
+
+    let mut regrouper = Regrouper {
+        items: rucksacks.iter().map(|rucksack| rucksack.items()).collect(),
+        group_size,
+        assigned: vec![false; rucksacks.len()],
+        groups: Vec::new(),
+        found: Vec::new(),
+    };
+    regrouper.place_next_group();
+
+    let unique = regrouper.found.len() == 1;
+    regrouper
+        .found
+        .into_iter()
+        .next()
+        .map(|groups| Regrouping { groups, unique })
+}
+
+pub fn explain(input: &str) -> ParseResult<String> {
+    let mut output = validate(input, 3)?.render();
+
+    if let Ok(rucksacks) = read_input(input) {
+        let regrouping = match regroup(&rucksacks, 3) {
+            Some(regrouping) if regrouping.unique => "unique",
+            Some(_) => "not unique",
+            None => "none",
+        };
+        output += &format!("\nGrouping without the line order: {}", regrouping);
+    }
+
+    Ok(output)
+}
+
+pub fn day3a(input: &str) -> ParseResult<String> {
+    let sum = read_input(input)?
+        .into_iter()
+        .enumerate()
+        .map(|(i, rucksack)| {
+            expect_single(rucksack.shared(), "item in both compartments")
+                .map_err(|error| error.at_line(i + 1))
+        })
+        .sum::<ParseResult<u32>>()?;
+
+    Ok(sum.to_string())
+}
+
+pub fn day3b(input: &str) -> ParseResult<String> {
+    Ok(badge_sum(&read_input(input)?, 3)?.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw
+";
+
+    #[test]
+    fn finds_shared_items_and_badges() {
+        assert_eq!(day3a(EXAMPLE).unwrap(), "157");
+        assert_eq!(day3b(EXAMPLE).unwrap(), "70");
+
+        let rucksacks = read_input(EXAMPLE).unwrap();
+        assert_eq!(rucksacks[0].shared().items().collect::<String>(), "p");
+        assert_eq!(badges(&rucksacks[..2]).items().collect::<String>(), "frsFM");
+        assert_eq!(badges(&rucksacks[..1]), rucksacks[0].items());
+        assert!(badge_sum(&rucksacks, 4).is_err());
+
+        let pairs = read_input("ax\nxb\ncy\nyd\n").unwrap();
+        assert_eq!(badge_sum(&pairs, 2).unwrap(), 24 + 25);
+    }
+
+    #[test]
+    fn flags_every_issue() {
+        assert!(validate(EXAMPLE, 0).is_err());
+        assert!(validate(EXAMPLE, 3).unwrap().passed());
+        assert!(validate(EXAMPLE, 3)
+            .unwrap()
+            .render()
+            .ends_with("6 rucksacks, 0 issues: PASS"));
+
+        let validation = validate("ab1a\nabb\nabcd\nabab\nxaxa\n", 2).unwrap();
+        assert!(!validation.passed());
+        assert_eq!(
+            validation.render(),
+            "line 1: invalid items `1`\n\
+             lines 1-2: badges: `ab`\n\
+             line 2: odd number of items (3)\n\
+             line 3: items in both compartments: none\n\
+             lines 3-4: badges: `ab`\n\
+             line 4: items in both compartments: `ab`\n\
+             line 5: items in both compartments: `ax`\n\
+             line 5: last group has 1 of 2 rucksacks\n\
+             5 rucksacks, 8 issues: FAIL"
+        );
+    }
+
+    #[test]
+    fn regroups_shuffled_rucksacks() {
+        let rucksacks = read_input(EXAMPLE).unwrap();
+        let shuffled = [4, 0, 2, 5, 3, 1].map(|i| rucksacks[i]);
+
+        let regrouping = regroup(&shuffled, 3).unwrap();
+        for group in &regrouping.groups {
+            let group = group.iter().map(|&i| shuffled[i]).collect::<Vec<_>>();
+            assert_eq!(badges(&group).len(), 1);
+        }
+        // The example's groups aren't the only ones sharing a single item
+        assert!(!regrouping.unique);
+
+        let pairs = read_input("cy\nax\nyd\nxb\n").unwrap();
+        let regrouping = regroup(&pairs, 2).unwrap();
+        assert_eq!(regrouping.groups, vec![vec![0, 2], vec![1, 3]]);
+        assert!(regrouping.unique);
+
+        let alike = read_input("aa\naa\naa\naa\n").unwrap();
+        assert!(!regroup(&alike, 2).unwrap().unique);
+
+        let apart = read_input("aa\nbb\n").unwrap();
+        assert_eq!(regroup(&apart, 2), None);
+        assert_eq!(regroup(&rucksacks, 4), None);
+    }
+
+    #[test]
+    fn reports_every_common_item() {
+        let error = day3a("aa\nabab\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2: expected one item in both compartments, found `ab`"
+        );
+
+        let error = day3b("ab\nab\nab\nab\nac\nad\n").unwrap_err();
+        assert_eq!(error.to_string(), "line 1: expected one badge, found `ab`");
+    }
+}
@@ -0,0 +1,94 @@
+use std::collections::HashMap;
+
+use crate::parse::{self, ParseResult};
+
+/// The first and last section of each elf's range
+type Pair = ((u32, u32), (u32, u32));
+
+fn parse_input(input: &str) -> ParseResult<Vec<Pair>> {
+    parse::parse_lines(input, |line| {
+        let (left, right) = parse::split_once(line, ",")?;
+
+        fn parse_range(s: &str) -> ParseResult<(u32, u32)> {
+            let (left, right) = parse::split_once(s, "-")?;
+            let left = parse::number(left)?;
+            let right = parse::number(right)?;
+            Ok((left, right))
+        }
+
+        Ok((parse_range(left)?, parse_range(right)?))
+    })
+}
+
+/// Entry point for fuzzing the parser.
+pub fn fuzz(input: &str) -> ParseResult<()> {
+    parse_input(input).map(drop)
+}
+
+fn classify(((a_l, a_r), (b_l, b_r)): Pair) -> &'static str {
+    if (a_l <= b_l && a_r >= b_r) || (b_l <= a_l && b_r >= a_r) {
+        "contained"
+    } else if a_l <= b_r && b_l <= a_r {
+        "overlapping"
+    } else {
+        "disjoint"
+    }
+}
+
+/// Every pair with how its ranges relate. Contained pairs count for both parts, overlapping ones
+/// only for the second.
+pub fn explain(input: &str) -> ParseResult<String> {
+    let pairs = parse_input(input)?;
+    let mut counts = HashMap::new();
+    let mut output = String::new();
+
+    for (line, pair) in input.lines().zip(pairs) {
+        let class = classify(pair);
+        *counts.entry(class).or_insert(0) += 1;
+        output += &format!("{:<16}{}\n", line, class);
+    }
+
+    for class in ["contained", "overlapping", "disjoint"] {
+        output += &format!("\n{}: {}", class, counts.get(class).unwrap_or(&0));
+    }
+
+    Ok(output)
+}
+
+pub fn day4a(input: &str) -> ParseResult<String> {
+    let input = parse_input(input)?;
+
+    let fully_contained_count = input
+        .into_iter()
+        .filter(|pair| classify(*pair) == "contained")
+        .count();
+
+    Ok(fully_contained_count.to_string())
+}
+
+pub fn day4b(input: &str) -> ParseResult<String> {
+    let input = parse_input(input)?;
+
+    let partially_contained_count = input
+        .into_iter()
+        .filter(|pair| classify(*pair) != "disjoint")
+        .count();
+
+    Ok(partially_contained_count.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_pairs() {
+        let input = "2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8\n";
+        let explanation = explain(input).unwrap();
+
+        assert!(explanation.starts_with("2-4,6-8         disjoint\n"));
+        assert!(explanation.ends_with("contained: 2\noverlapping: 2\ndisjoint: 2"));
+        assert_eq!(day4a(input).unwrap(), "2");
+        assert_eq!(day4b(input).unwrap(), "4");
+    }
+}
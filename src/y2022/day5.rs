@@ -0,0 +1,311 @@
+use std::collections::VecDeque;
+
+use itertools::Itertools;
+use once_cell::sync::OnceCell;
+use regex::Regex;
+
+use crate::{
+    debugger::Simulation,
+    parse::{self, ParseError, ParseResult},
+};
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct Move {
+    count: usize,
+    from: usize,
+    to: usize,
+}
+
+fn parse_stacks(diagram: &str) -> ParseResult<Vec<VecDeque<char>>> {
+    let stack_lines = diagram
+        .lines()
+        .map(|line| line.chars().collect_vec())
+        .collect_vec();
+
+    let (stack_numbers_line, stack_lines) = stack_lines
+        .split_last()
+        .ok_or_else(|| ParseError::new("missing stack diagram"))?;
+
+    let number_line = stack_lines.len() + 1;
+
+    // Every crate has to sit on top of a numbered stack
+    for (i, line) in stack_lines.iter().enumerate() {
+        for (column, ch) in line.iter().enumerate() {
+            if !ch.is_ascii_alphabetic() {
+                continue;
+            }
+
+            if !stack_numbers_line
+                .get(column)
+                .is_some_and(|ch| ch.is_ascii_digit())
+            {
+                return Err(ParseError::new(format!(
+                    "crate `{}` in column {} is not above a stack number",
+                    ch,
+                    column + 1
+                ))
+                .at_line(i + 1));
+            }
+        }
+    }
+
+    let mut stacks = Vec::new();
+
+    for (i, ch) in stack_numbers_line.iter().enumerate() {
+        if !ch.is_digit(10) {
+            continue;
+        }
+
+        let mut stack = VecDeque::new();
+
+        for (line_index, line) in stack_lines.iter().enumerate() {
+            match line.get(i).copied() {
+                Some(ch) if ch.is_ascii_alphabetic() => stack.push_back(ch),
+                _ if !stack.is_empty() => {
+                    return Err(ParseError::new(format!(
+                        "floating crate in stack {}",
+                        stacks.len() + 1
+                    ))
+                    .at_line(line_index + 1));
+                }
+                _ => {}
+            }
+        }
+
+        stacks.push(stack);
+    }
+
+    if stacks.is_empty() {
+        return Err(ParseError::new("no stack numbers").at_line(number_line));
+    }
+
+    Ok(stacks)
+}
+
+fn parse_move(line: &str, stack_count: usize) -> ParseResult<Move> {
+    static REGEX: OnceCell<Regex> = OnceCell::new();
+
+    let regex = REGEX.get_or_init(|| Regex::new(r"^move (\d+) from (\d+) to (\d+)$").unwrap());
+
+    let captures = regex
+        .captures(line)
+        .ok_or_else(|| ParseError::new(format!("invalid move `{}`", line)))?;
+
+    let stack = |i| -> ParseResult<usize> {
+        let number: usize = parse::number(&captures[i])?;
+
+        if !(1..=stack_count).contains(&number) {
+            return Err(ParseError::new(format!("no stack {}", number)));
+        }
+
+        Ok(number - 1)
+    };
+
+    Ok(Move {
+        count: parse::number(&captures[1])?,
+        from: stack(2)?,
+        to: stack(3)?,
+    })
+}
+
+fn parse_input(input: &str) -> ParseResult<(Vec<VecDeque<char>>, Vec<Move>)> {
+    let (diagram, instructions) = parse::split_once(input, "\n\n")?;
+
+    let stacks = parse_stacks(diagram)?;
+
+    // The moves start after the diagram and the blank line
+    let first_move_line = diagram.lines().count() + 2;
+
+    let moves = parse::parse_lines(instructions, |line| parse_move(line, stacks.len())).map_err(
+        |error| ParseError {
+            line: error.line.map(|line| line + first_move_line - 1),
+            ..error
+        },
+    )?;
+
+    // Both parts move the same number of crates, so the heights alone tell whether a stack
+    // runs out
+    let mut heights = stacks.iter().map(VecDeque::len).collect_vec();
+
+    for (i, instruction) in moves.iter().enumerate() {
+        if instruction.count > heights[instruction.from] {
+            return Err(ParseError::new(format!(
+                "can't move {} crates from stack {} holding {}",
+                instruction.count,
+                instruction.from + 1,
+                heights[instruction.from]
+            ))
+            .at_line(first_move_line + i));
+        }
+
+        heights[instruction.from] -= instruction.count;
+        heights[instruction.to] += instruction.count;
+    }
+
+    Ok((stacks, moves))
+}
+
+/// Entry point for fuzzing the parser.
+pub fn fuzz(input: &str) -> ParseResult<()> {
+    parse_input(input).map(drop)
+}
+
+fn apply_instruction(stacks: &mut Vec<VecDeque<char>>, instruction: Move, move_whole_stack: bool) {
+    let mut moved = stacks[instruction.from]
+        .drain(..instruction.count)
+        .collect_vec();
+
+    if move_whole_stack {
+        moved.reverse();
+    }
+
+    for ch in moved {
+        stacks[instruction.to].push_front(ch);
+    }
+}
+
+fn render_stacks(stacks: &[VecDeque<char>]) -> String {
+    let height = stacks.iter().map(|stack| stack.len()).max().unwrap_or(0);
+
+    let mut lines = (0..height)
+        .rev()
+        .map(|level| {
+            stacks
+                .iter()
+                .map(|stack| match stack.len().checked_sub(level + 1) {
+                    Some(i) => format!("[{}]", stack[i]),
+                    None => "   ".to_string(),
+                })
+                .join(" ")
+                .trim_end()
+                .to_string()
+        })
+        .collect_vec();
+
+    lines.push(
+        (1..=stacks.len())
+            .map(|i| format!(" {} ", i))
+            .join(" ")
+            .trim_end()
+            .to_string(),
+    );
+
+    lines.join("\n")
+}
+
+#[derive(Clone)]
+pub struct CrateSimulation {
+    stacks: Vec<VecDeque<char>>,
+    moves: Vec<Move>,
+    next_move: usize,
+    move_whole_stack: bool,
+}
+
+impl CrateSimulation {
+    pub fn new(input: &str, move_whole_stack: bool) -> ParseResult<Self> {
+        let (stacks, moves) = parse_input(input)?;
+
+        Ok(Self {
+            stacks,
+            moves,
+            next_move: 0,
+            move_whole_stack,
+        })
+    }
+}
+
+impl Simulation for CrateSimulation {
+    fn step(&mut self) -> bool {
+        let Some(instruction) = self.moves.get(self.next_move).cloned() else {
+            return false;
+        };
+
+        apply_instruction(&mut self.stacks, instruction, self.move_whole_stack);
+        self.next_move += 1;
+        true
+    }
+
+    fn render(&self) -> String {
+        let next_move = match self.moves.get(self.next_move) {
+            Some(instruction) => format!(
+                "Next move ({}/{}): move {} from {} to {}",
+                self.next_move + 1,
+                self.moves.len(),
+                instruction.count,
+                instruction.from + 1,
+                instruction.to + 1
+            ),
+            None => format!("All {} moves done", self.moves.len()),
+        };
+
+        format!("{}\n\n{}", render_stacks(&self.stacks), next_move)
+    }
+}
+
+/// The crates on top of the stacks. An empty stack has none and adds nothing.
+fn stacks_to_answer(stacks: Vec<VecDeque<char>>) -> String {
+    stacks
+        .into_iter()
+        .filter_map(|stack| stack.front().copied())
+        .collect()
+}
+
+pub fn day5a(input: &str) -> ParseResult<String> {
+    let (mut stacks, instructions) = parse_input(input)?;
+
+    for instruction in instructions {
+        apply_instruction(&mut stacks, instruction, false);
+    }
+
+    Ok(stacks_to_answer(stacks))
+}
+
+pub fn day5b(input: &str) -> ParseResult<String> {
+    let (mut stacks, instructions) = parse_input(input)?;
+
+    for instruction in instructions {
+        apply_instruction(&mut stacks, instruction, true);
+    }
+
+    Ok(stacks_to_answer(stacks))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapshot::assert_snapshot;
+
+    const EXAMPLE: &str = "    [D]    
+[N] [C]    
+[Z] [M] [P]
+ 1   2   3 
+
+move 1 from 2 to 1
+move 3 from 1 to 3
+move 2 from 2 to 1
+move 1 from 1 to 2
+";
+
+    #[test]
+    fn renders_stacks() {
+        let (stacks, _) = parse_input(EXAMPLE).unwrap();
+        assert_snapshot("day5_start", &render_stacks(&stacks));
+
+        for (move_whole_stack, name) in [(false, "day5a_end"), (true, "day5b_end")] {
+            let mut simulation = CrateSimulation::new(EXAMPLE, move_whole_stack).unwrap();
+            while simulation.step() {}
+
+            assert_snapshot(name, &render_stacks(&simulation.stacks));
+        }
+    }
+
+    #[test]
+    fn rejects_moving_more_crates_than_a_stack_holds() {
+        let error =
+            parse_input("[A]\n 1   2 \n\nmove 1 from 1 to 2\nmove 2 from 2 to 1\n").unwrap_err();
+        assert_eq!(error.line, Some(5));
+
+        let input = "[A]\n 1   2 \n\nmove 1 from 1 to 2\n";
+        assert_eq!(day5a(input).unwrap(), "A");
+    }
+}
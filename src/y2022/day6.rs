@@ -0,0 +1,32 @@
+use itertools::Itertools;
+
+use crate::{parse::ParseResult, util::to_set};
+
+fn read_input(input: &str) -> Vec<char> {
+    input.chars().collect()
+}
+
+/// Entry point for fuzzing the parser. Any input is a valid datastream.
+pub fn fuzz(input: &str) -> ParseResult<()> {
+    read_input(input);
+    Ok(())
+}
+
+fn find_marker_offset(input: &[char], length: usize) -> usize {
+    let (pos, _) = input
+        .windows(length)
+        .find_position(|window| to_set(*window).len() == length)
+        .unwrap();
+
+    pos + length
+}
+
+pub fn day6a(input: &str) -> ParseResult<String> {
+    let input = read_input(input);
+    Ok(find_marker_offset(&input, 4).to_string())
+}
+
+pub fn day6b(input: &str) -> ParseResult<String> {
+    let input = read_input(input);
+    Ok(find_marker_offset(&input, 14).to_string())
+}
@@ -0,0 +1,258 @@
+use std::{collections::HashMap, iter::Peekable};
+
+use itertools::Itertools;
+
+use crate::parse::{self, ParseError, ParseResult};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct File {
+    size: usize,
+    name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Folder {
+    name: String,
+    files: HashMap<String, File>,
+    folders: HashMap<String, Folder>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Path(Vec<String>);
+
+impl Folder {
+    fn new(name: String) -> Self {
+        Self {
+            name,
+            files: HashMap::new(),
+            folders: HashMap::new(),
+        }
+    }
+
+    fn get_folder_mut(&mut self, path: &Path) -> Option<&mut Self> {
+        if path.0.is_empty() {
+            return Some(self);
+        }
+
+        let (name, rest) = path.0.split_first().unwrap();
+
+        self.folders
+            .get_mut(name)
+            .and_then(|folder| folder.get_folder_mut(&Path(rest.to_vec())))
+    }
+
+    fn add_folder(&mut self, folder: Folder) {
+        self.folders.insert(folder.name.clone(), folder);
+    }
+
+    fn add_file(&mut self, file: File) {
+        self.files.insert(file.name.clone(), file);
+    }
+
+    fn get_size(&self) -> usize {
+        let mut size = 0;
+
+        for file in self.files.values() {
+            size += file.size;
+        }
+
+        for folder in self.folders.values() {
+            size += folder.get_size();
+        }
+
+        size
+    }
+}
+
+#[derive(Debug, Clone)]
+struct State {
+    file_system: Folder,
+    current_folder: Path,
+}
+
+impl State {
+    fn new() -> Self {
+        Self {
+            file_system: Folder::new("".to_string()),
+            current_folder: Path(vec![]),
+        }
+    }
+}
+
+fn execute_lines(lines: &[&str], state: &mut State) -> ParseResult<()> {
+    let mut lines = lines.iter().enumerate().peekable();
+
+    while let Some((i, line)) = lines.next() {
+        execute_command(line, &mut lines, state).map_err(|error| error.at_line(i + 1))?;
+    }
+
+    Ok(())
+}
+
+fn execute_command<'a>(
+    line: &str,
+    lines: &mut Peekable<impl Iterator<Item = (usize, &'a &'a str)>>,
+    state: &mut State,
+) -> ParseResult<()> {
+    let command_parts = match line.strip_prefix("$") {
+        Some(command) => command.split_whitespace().collect_vec(),
+        None => {
+            return Err(ParseError::new(format!(
+                "expected a command, got `{}`",
+                line
+            )))
+        }
+    };
+
+    match command_parts[..] {
+        ["cd", "/"] => state.current_folder = Path(vec![]),
+        ["cd", ".."] => {
+            state.current_folder.0.pop();
+        }
+        ["cd", name] => {
+            state.current_folder.0.push(name.to_string());
+
+            if state
+                .file_system
+                .get_folder_mut(&state.current_folder)
+                .is_none()
+            {
+                return Err(ParseError::new(format!("no such directory `{}`", name)));
+            }
+        }
+        ["ls"] => {
+            while let Some((i, line)) = lines.next_if(|(_, line)| !line.starts_with("$")) {
+                list_entry(line, state).map_err(|error| error.at_line(i + 1))?;
+            }
+        }
+        _ => {
+            return Err(ParseError::new(format!(
+                "unknown command `{}`",
+                command_parts.join(" ")
+            )))
+        }
+    }
+
+    Ok(())
+}
+
+fn list_entry(line: &str, state: &mut State) -> ParseResult<()> {
+    let folder = state
+        .file_system
+        .get_folder_mut(&state.current_folder)
+        .ok_or_else(|| ParseError::new("listing a directory that doesn't exist"))?;
+
+    match line.split_whitespace().collect_vec()[..] {
+        ["dir", folder_name] => folder.add_folder(Folder::new(folder_name.to_string())),
+        [size, name] => folder.add_file(File {
+            size: parse::number(size)?,
+            name: name.to_string(),
+        }),
+        _ => return Err(ParseError::new(format!("invalid listing `{}`", line))),
+    }
+
+    Ok(())
+}
+
+fn parse_input(input: &str) -> ParseResult<State> {
+    let lines = input.lines().collect_vec();
+    let mut state = State::new();
+    execute_lines(&lines, &mut state)?;
+    Ok(state)
+}
+
+/// Entry point for fuzzing the parser.
+pub fn fuzz(input: &str) -> ParseResult<()> {
+    parse_input(input).map(drop)
+}
+
+fn render_tree(folder: &Folder, depth: usize, output: &mut String) {
+    let indent = "  ".repeat(depth);
+    let name = if depth == 0 { "/" } else { &folder.name };
+
+    output.push_str(&format!(
+        "{}- {} (dir, size={})\n",
+        indent,
+        name,
+        folder.get_size()
+    ));
+
+    for name in folder.folders.keys().sorted() {
+        render_tree(&folder.folders[name], depth + 1, output);
+    }
+
+    for file in folder.files.values().sorted_by_key(|file| &file.name) {
+        output.push_str(&format!(
+            "{}  - {} (file, size={})\n",
+            indent, file.name, file.size
+        ));
+    }
+}
+
+/// The directory tree with the total size of every directory.
+pub fn explain(input: &str) -> ParseResult<String> {
+    let state = parse_input(input)?;
+
+    let mut output = String::new();
+    render_tree(&state.file_system, 0, &mut output);
+
+    Ok(output)
+}
+
+fn sum_subfolder_sizes(folder: &Folder, size: &mut usize) {
+    let folder_size = folder.get_size();
+
+    if folder_size <= 100_000 {
+        *size += folder_size;
+    }
+
+    for subfolder in folder.folders.values() {
+        sum_subfolder_sizes(subfolder, size);
+    }
+}
+
+pub fn day7a(input: &str) -> ParseResult<String> {
+    let state = parse_input(input)?;
+
+    let mut size = 0;
+    sum_subfolder_sizes(&state.file_system, &mut size);
+
+    Ok(size.to_string())
+}
+
+fn find_folder_to_remove<'a, 'b>(
+    folder: &'a Folder,
+    min_size: usize,
+    candidates: &'b mut Vec<(usize, &'a Folder)>,
+) {
+    let folder_size = folder.get_size();
+
+    if folder_size > min_size {
+        candidates.push((folder_size, folder));
+    }
+
+    for subfolder in folder.folders.values() {
+        find_folder_to_remove(subfolder, min_size, candidates);
+    }
+}
+
+pub fn day7b(input: &str) -> ParseResult<String> {
+    let state = parse_input(input)?;
+
+    let total_size = state.file_system.get_size();
+    let free_space = 70000000_usize
+        .checked_sub(total_size)
+        .ok_or_else(|| ParseError::new("the files don't fit on the disk"))?;
+    let min_delete_size = 30000000_usize.saturating_sub(free_space);
+
+    let mut candidates = vec![];
+    find_folder_to_remove(&state.file_system, min_delete_size, &mut candidates);
+
+    let smallest_candidate = candidates
+        .iter()
+        .min_by_key(|(size, _)| *size)
+        .unwrap()
+        .clone();
+
+    Ok(smallest_candidate.0.to_string())
+}
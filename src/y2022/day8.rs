@@ -1,4 +1,4 @@
-const INPUT: &str = include_str!("./day8.input");
+use crate::parse::{self, ParseError, ParseResult};
 
 struct Map {
     width: usize,
@@ -89,24 +89,46 @@ impl Map {
     }
 }
 
-fn parse_input() -> Map {
-    let height = INPUT.lines().count();
-    let width = INPUT.lines().next().unwrap().len();
+fn parse_input(input: &str) -> ParseResult<Map> {
+    let height = input.lines().count();
+    let width = input.lines().next().map_or(0, str::len);
 
-    let data = INPUT
-        .lines()
-        .flat_map(|line| line.chars().map(|ch| ch.to_digit(10).unwrap() as u8))
-        .collect();
+    if width == 0 {
+        return Err(ParseError::new("empty map"));
+    }
+
+    let rows = parse::parse_lines(input, |line| {
+        if line.len() != width {
+            return Err(ParseError::new(format!(
+                "expected {} trees, got {}",
+                width,
+                line.len()
+            )));
+        }
+
+        line.chars()
+            .map(|ch| {
+                ch.to_digit(10)
+                    .map(|height| height as u8)
+                    .ok_or_else(|| ParseError::new(format!("invalid tree height `{}`", ch)))
+            })
+            .collect::<ParseResult<Vec<_>>>()
+    })?;
 
-    Map {
+    Ok(Map {
         width,
         height,
-        data,
-    }
+        data: rows.concat(),
+    })
+}
+
+/// Entry point for fuzzing the parser.
+pub fn fuzz(input: &str) -> ParseResult<()> {
+    parse_input(input).map(drop)
 }
 
-pub fn day8a() {
-    let map = parse_input();
+pub fn day8a(input: &str) -> ParseResult<String> {
+    let map = parse_input(input)?;
     let mut visible = 0;
 
     for y in 0..map.height {
@@ -117,11 +139,11 @@ pub fn day8a() {
         }
     }
 
-    println!("Day 8a: {}", visible);
+    Ok(visible.to_string())
 }
 
-pub fn day8b() {
-    let map = parse_input();
+pub fn day8b(input: &str) -> ParseResult<String> {
+    let map = parse_input(input)?;
     let mut highest_viewing_distance = 0;
 
     for y in 0..map.height {
@@ -133,5 +155,5 @@ pub fn day8b() {
         }
     }
 
-    println!("Day 8b: {}", highest_viewing_distance);
+    Ok(highest_viewing_distance.to_string())
 }
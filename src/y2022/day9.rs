@@ -0,0 +1,262 @@
+use std::collections::HashSet;
+
+use itertools::Itertools;
+
+use crate::{
+    debugger::Simulation,
+    parse::{self, ParseError, ParseResult},
+};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum CardinalDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum DiagonalDirection {
+    UpLeft,
+    UpRight,
+    DownLeft,
+    DownRight,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Direction {
+    Cardinal(CardinalDirection),
+    Diagonal(DiagonalDirection),
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+struct Instruction(CardinalDirection, i64);
+
+fn read_input(input: &str) -> ParseResult<Vec<Instruction>> {
+    parse::parse_lines(input, |line| {
+        let Some((direction, distance)) = line.split_ascii_whitespace().collect_tuple() else {
+            return Err(ParseError::new(format!("invalid instruction `{}`", line)));
+        };
+
+        let direction = match direction {
+            "U" => CardinalDirection::Up,
+            "D" => CardinalDirection::Down,
+            "L" => CardinalDirection::Left,
+            "R" => CardinalDirection::Right,
+            _ => {
+                return Err(ParseError::new(format!(
+                    "invalid direction `{}`",
+                    direction
+                )))
+            }
+        };
+
+        let distance: u32 = parse::number(distance)?;
+        Ok(Instruction(direction, distance.into()))
+    })
+}
+
+/// Entry point for fuzzing the parser.
+pub fn fuzz(input: &str) -> ParseResult<()> {
+    read_input(input).map(drop)
+}
+
+fn move_coord((x, y): (i64, i64), direction: Direction) -> (i64, i64) {
+    match direction {
+        Direction::Cardinal(CardinalDirection::Up) => (x, y - 1),
+        Direction::Cardinal(CardinalDirection::Down) => (x, y + 1),
+        Direction::Cardinal(CardinalDirection::Left) => (x - 1, y),
+        Direction::Cardinal(CardinalDirection::Right) => (x + 1, y),
+        Direction::Diagonal(DiagonalDirection::UpLeft) => (x - 1, y - 1),
+        Direction::Diagonal(DiagonalDirection::UpRight) => (x + 1, y - 1),
+        Direction::Diagonal(DiagonalDirection::DownLeft) => (x - 1, y + 1),
+        Direction::Diagonal(DiagonalDirection::DownRight) => (x + 1, y + 1),
+    }
+}
+
+fn are_touching((head_x, head_y): (i64, i64), (tail_x, tail_y): (i64, i64)) -> bool {
+    let x_diff = head_x - tail_x;
+    let y_diff = head_y - tail_y;
+
+    x_diff.abs() <= 1 && y_diff.abs() <= 1
+}
+
+fn get_direction_to_move_to(head: (i64, i64), tail: (i64, i64)) -> Option<Direction> {
+    if are_touching(head, tail) {
+        return None;
+    }
+
+    let (head_x, head_y) = head;
+    let (tail_x, tail_y) = tail;
+
+    let x_diff = tail_x - head_x;
+    let y_diff = tail_y - head_y;
+
+    if y_diff == 0 {
+        if x_diff > 0 {
+            Some(Direction::Cardinal(CardinalDirection::Left))
+        } else {
+            Some(Direction::Cardinal(CardinalDirection::Right))
+        }
+    } else if x_diff == 0 {
+        if y_diff > 0 {
+            Some(Direction::Cardinal(CardinalDirection::Up))
+        } else {
+            Some(Direction::Cardinal(CardinalDirection::Down))
+        }
+    } else if x_diff > 0 && y_diff > 0 {
+        Some(Direction::Diagonal(DiagonalDirection::UpLeft))
+    } else if x_diff > 0 && y_diff < 0 {
+        Some(Direction::Diagonal(DiagonalDirection::DownLeft))
+    } else if x_diff < 0 && y_diff > 0 {
+        Some(Direction::Diagonal(DiagonalDirection::UpRight))
+    } else {
+        Some(Direction::Diagonal(DiagonalDirection::DownRight))
+    }
+}
+
+/// Moves the head one step and lets every following knot catch up with the one before it.
+fn move_rope(knots: &mut [(i64, i64)], direction: CardinalDirection) {
+    knots[0] = move_coord(knots[0], Direction::Cardinal(direction));
+
+    for i in 1..knots.len() {
+        if let Some(direction) = get_direction_to_move_to(knots[i - 1], knots[i]) {
+            knots[i] = move_coord(knots[i], direction);
+        }
+    }
+}
+
+fn count_tail_positions(input: &str, knot_count: usize) -> ParseResult<String> {
+    let input = read_input(input)?;
+    let mut knots = vec![(0, 0); knot_count];
+    let mut tail_visited_positions = HashSet::from([knots[knot_count - 1]]);
+
+    for Instruction(direction, distance) in input {
+        for _ in 0..distance {
+            move_rope(&mut knots, direction);
+            tail_visited_positions.insert(knots[knot_count - 1]);
+        }
+    }
+
+    Ok(tail_visited_positions.len().to_string())
+}
+
+#[derive(Clone)]
+pub struct RopeSimulation {
+    knots: Vec<(i64, i64)>,
+    instructions: Vec<Instruction>,
+    next_instruction: usize,
+    remaining_distance: i64,
+    tail_visited_positions: HashSet<(i64, i64)>,
+}
+
+impl RopeSimulation {
+    pub fn new(input: &str, knot_count: usize) -> ParseResult<Self> {
+        let knots = vec![(0, 0); knot_count];
+
+        Ok(Self {
+            tail_visited_positions: HashSet::from([knots[0]]),
+            knots,
+            instructions: read_input(input)?,
+            next_instruction: 0,
+            remaining_distance: 0,
+        })
+    }
+
+    fn render_grid(&self) -> Option<String> {
+        // Frame the knots and the starting point
+        let points = self.knots.iter().chain([(0, 0)].iter());
+        let (min_x, max_x) = points.clone().map(|(x, _)| *x).minmax().into_option()?;
+        let (min_y, max_y) = points.map(|(_, y)| *y).minmax().into_option()?;
+
+        if max_x - min_x > 60 || max_y - min_y > 30 {
+            return None;
+        }
+
+        let mut lines = Vec::new();
+
+        for y in min_y - 1..=max_y + 1 {
+            let mut line = String::new();
+
+            for x in min_x - 1..=max_x + 1 {
+                let knot = self.knots.iter().position(|knot| *knot == (x, y));
+
+                line.push(match knot {
+                    Some(0) => 'H',
+                    Some(i) if i == self.knots.len() - 1 => 'T',
+                    Some(i) => char::from_digit(i as u32 % 10, 10).unwrap(),
+                    None if (x, y) == (0, 0) => 's',
+                    None if self.tail_visited_positions.contains(&(x, y)) => '#',
+                    None => '.',
+                });
+            }
+
+            lines.push(line);
+        }
+
+        Some(lines.join("\n"))
+    }
+}
+
+impl Simulation for RopeSimulation {
+    fn step(&mut self) -> bool {
+        while self.remaining_distance == 0 {
+            let Some(instruction) = self.instructions.get(self.next_instruction) else {
+                return false;
+            };
+
+            self.remaining_distance = instruction.1;
+            self.next_instruction += 1;
+        }
+
+        let direction = self.instructions[self.next_instruction - 1].0;
+        move_rope(&mut self.knots, direction);
+        self.remaining_distance -= 1;
+
+        if let Some(&tail) = self.knots.last() {
+            self.tail_visited_positions.insert(tail);
+        }
+
+        true
+    }
+
+    fn render(&self) -> String {
+        let instruction = match self.next_instruction.checked_sub(1) {
+            Some(i) if self.remaining_distance > 0 => format!(
+                "{:?} {} ({} steps left)",
+                self.instructions[i].0, self.instructions[i].1, self.remaining_distance
+            ),
+            _ => "-".to_string(),
+        };
+
+        let knots = self
+            .knots
+            .iter()
+            .map(|(x, y)| format!("({}, {})", x, y))
+            .join(" ");
+
+        let mut output = format!(
+            "Instruction {}/{}: {}\nKnots: {}\nTail visited: {}",
+            self.next_instruction,
+            self.instructions.len(),
+            instruction,
+            knots,
+            self.tail_visited_positions.len()
+        );
+
+        if let Some(grid) = self.render_grid() {
+            output.push_str("\n\n");
+            output.push_str(&grid);
+        }
+
+        output
+    }
+}
+
+pub fn day9a(input: &str) -> ParseResult<String> {
+    count_tail_positions(input, 2)
+}
+
+pub fn day9b(input: &str) -> ParseResult<String> {
+    count_tail_positions(input, 10)
+}
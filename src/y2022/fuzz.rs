@@ -0,0 +1,104 @@
+//! Parsers and seed inputs for the fuzzer.
+
+use super::{
+    day1, day10, day11, day12, day13, day14, day15, day16a, day16b, day2, day3, day4, day5, day6,
+    day7, day8, day9,
+};
+use crate::fuzz::{target, Target};
+
+pub const TARGETS: &[Target] = &[
+    target("day1", day1::fuzz, "1000\n2000\n3000\n\n4000\n\n5000\n6000\n"),
+    target("day2", day2::fuzz, "A Y\nB X\nC Z\n"),
+    target(
+        "day3",
+        day3::fuzz,
+        "vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\nPmmdzqPrVvPwwTWBwg\n",
+    ),
+    target("day4", day4::fuzz, "2-4,6-8\n2-3,4-5\n5-7,7-9\n"),
+    target(
+        "day5",
+        day5::fuzz,
+        "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\nmove 1 from 2 to 1\nmove 3 from 1 to 3\n",
+    ),
+    target("day6", day6::fuzz, "mjqjpqmgbljsphdztnvjfqwrcgsmlb\n"),
+    target(
+        "day7",
+        day7::fuzz,
+        "$ cd /\n$ ls\ndir a\n14848514 b.txt\n$ cd a\n$ ls\n29116 f\n$ cd ..\n",
+    ),
+    target("day8", day8::fuzz, "30373\n25512\n65332\n"),
+    target("day9", day9::fuzz, "R 4\nU 4\nL 3\nD 1\n"),
+    target("day10", day10::fuzz, "noop\naddx 3\naddx -5\n"),
+    target(
+        "day11",
+        day11::fuzz,
+        "Monkey 0:\n  Starting items: 79, 98\n  Operation: new = old * 19\n  Test: divisible by 23\n    If true: throw to monkey 1\n    If false: throw to monkey 1\n\nMonkey 1:\n  Starting items: 54\n  Operation: new = old + 6\n  Test: divisible by 19\n    If true: throw to monkey 0\n    If false: throw to monkey 0\n",
+    ),
+    target("day12", day12::fuzz, "Sabqponm\nabcryxxl\naccszExk\n"),
+    target(
+        "day13",
+        day13::fuzz,
+        "[1,1,3,1,1]\n[1,1,5,1,1]\n\n[[1],[2,3,4]]\n[[1],4]\n\n[]\n[[[]]]\n",
+    ),
+    target(
+        "day14",
+        day14::fuzz,
+        "498,4 -> 498,6 -> 496,6\n503,4 -> 502,4 -> 502,9 -> 494,9\n",
+    ),
+    target(
+        "day15",
+        day15::fuzz,
+        "Sensor at x=2, y=18: closest beacon is at x=-2, y=15\nSensor at x=9, y=16: closest beacon is at x=10, y=16\n",
+    ),
+    target("day16a", day16a::fuzz, DAY16_SEED),
+    target("day16b", day16b::fuzz, DAY16_SEED),
+];
+
+const DAY16_SEED: &str = "Valve AA has flow rate=0; tunnels lead to valves DD, BB\n\
+                          Valve BB has flow rate=13; tunnels lead to valves CC, AA\n\
+                          Valve CC has flow rate=2; tunnel leads to valve BB\n\
+                          Valve DD has flow rate=20; tunnels lead to valves AA, EE\n\
+                          Valve EE has flow rate=0; tunnel leads to valve DD\n";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fuzz::fuzz;
+
+    #[test]
+    fn seeds_are_valid() {
+        for target in TARGETS {
+            assert_eq!((target.parse)(target.seed), Ok(()), "{}", target.name);
+        }
+    }
+
+    #[test]
+    fn parsers_survive_fuzzing() {
+        for target in TARGETS {
+            if let Err(crash) = fuzz(target, 2_000, 2022) {
+                panic!(
+                    "{} panicked with `{}` on {:?}",
+                    target.name,
+                    crash.message,
+                    String::from_utf8_lossy(&crash.input)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_input() {
+        // Unterminated and unseparated lists
+        assert!(day13::fuzz("[1,[2]\n[1]\n").is_err());
+        assert!(day13::fuzz("[1[2]]\n[1]\n").is_err());
+        // Unknown command, listing a directory that was never seen
+        assert!(day7::fuzz("$ cd /\n$ rm -rf a\n").is_err());
+        assert!(day7::fuzz("$ cd /\n$ cd a\n$ ls\n1 b\n").is_err());
+        // Crate that isn't above a stack, move from a missing stack
+        assert!(day5::fuzz("[A]     [B]\n 1   2\n\nmove 1 from 1 to 2\n").is_err());
+        assert!(day5::fuzz("[A]\n 1 \n\nmove 1 from 0 to 1\n").is_err());
+
+        let error = day5::fuzz("[A] [B]\n 1   2 \n\nmove 1 from 1 to 2\nmove 1 from 3 to 1\n");
+        assert_eq!(error.unwrap_err().to_string(), "line 5: no stack 3");
+    }
+}
@@ -0,0 +1,132 @@
+//! Advent of Code 2022.
+
+use std::error::Error;
+
+use crate::{
+    debugger::{self, Debugger},
+    parse::ParseResult,
+    solution::{Part, Solution, Year},
+};
+
+#[allow(dead_code)]
+mod day1;
+#[allow(dead_code)]
+mod day10;
+#[allow(dead_code)]
+mod day11;
+#[allow(dead_code)]
+mod day12;
+#[allow(dead_code)]
+mod day13;
+#[allow(dead_code)]
+mod day14;
+#[allow(dead_code)]
+mod day15;
+#[allow(dead_code)]
+mod day2;
+#[allow(dead_code)]
+mod day3;
+#[allow(dead_code)]
+mod day4;
+#[allow(dead_code)]
+mod day5;
+#[allow(dead_code)]
+mod day6;
+#[allow(dead_code)]
+mod day7;
+#[allow(dead_code)]
+mod day8;
+#[allow(dead_code)]
+mod day9;
+
+#[allow(dead_code)]
+mod day16a;
+#[allow(dead_code)]
+mod day16b;
+
+mod fuzz;
+
+pub const YEAR: Year = Year {
+    year: 2022,
+    solutions: SOLUTIONS,
+    fuzz_targets: fuzz::TARGETS,
+    explain,
+    debug,
+};
+
+pub const SOLUTIONS: &[Solution] = &[
+    Solution::new(2022, 1, Part::A, day1::day1a),
+    Solution::new(2022, 1, Part::B, day1::day1b),
+    Solution::new(2022, 2, Part::A, day2::day2a),
+    Solution::new(2022, 2, Part::B, day2::day2b),
+    Solution::new(2022, 3, Part::A, day3::day3a),
+    Solution::new(2022, 3, Part::B, day3::day3b),
+    Solution::new(2022, 4, Part::A, day4::day4a),
+    Solution::new(2022, 4, Part::B, day4::day4b),
+    Solution::new(2022, 5, Part::A, day5::day5a),
+    Solution::new(2022, 5, Part::B, day5::day5b),
+    Solution::new(2022, 6, Part::A, day6::day6a),
+    Solution::new(2022, 6, Part::B, day6::day6b),
+    Solution::new(2022, 7, Part::A, day7::day7a),
+    Solution::new(2022, 7, Part::B, day7::day7b),
+    Solution::new(2022, 8, Part::A, day8::day8a),
+    Solution::new(2022, 8, Part::B, day8::day8b),
+    Solution::new(2022, 9, Part::A, day9::day9a),
+    Solution::new(2022, 9, Part::B, day9::day9b),
+    Solution::new(2022, 10, Part::A, day10::day10a),
+    Solution::new(2022, 10, Part::B, day10::day10b),
+    Solution::new(2022, 11, Part::A, day11::day11a),
+    Solution::new(2022, 11, Part::B, day11::day11b),
+    Solution::new(2022, 12, Part::A, day12::day12a),
+    Solution::new(2022, 12, Part::B, day12::day12b),
+    Solution::new(2022, 13, Part::A, day13::day13a),
+    Solution::new(2022, 13, Part::B, day13::day13b),
+    Solution::new(2022, 14, Part::A, day14::day14a),
+    Solution::new(2022, 14, Part::B, day14::day14b),
+    Solution::new(2022, 15, Part::A, day15::day15a),
+    Solution::new(2022, 15, Part::B, day15::day15b),
+    Solution::new(2022, 16, Part::A, day16a::day16a),
+    Solution::new(2022, 16, Part::B, day16b::day16b),
+];
+
+/// Dumps of the intermediate data the solvers work with, for finding out where a wrong answer
+/// goes wrong.
+fn explain(day: u8, input: &str) -> Option<ParseResult<String>> {
+    let explain = match day {
+        1 => day1::explain,
+        2 => day2::explain,
+        3 => day3::explain,
+        4 => day4::explain,
+        7 => day7::explain,
+        11 => day11::explain,
+        12 => day12::explain,
+        13 => day13::explain,
+        15 => day15::explain,
+        16 => day16b::explain,
+        _ => return None,
+    };
+
+    Some(explain(input))
+}
+
+fn debug(solution: &Solution, input: &str) -> Result<(), Box<dyn Error>> {
+    let is_b = solution.part == Part::B;
+
+    match solution.day {
+        5 => debugger::interact(Debugger::new(day5::CrateSimulation::new(input, is_b)?)),
+        9 => debugger::interact(Debugger::new(day9::RopeSimulation::new(
+            input,
+            if is_b { 10 } else { 2 },
+        )?)),
+        10 => debugger::interact(Debugger::new(day10::VmSimulation::new(input)?)),
+        11 => debugger::interact(Debugger::new(day11::MonkeySimulation::new(
+            input,
+            if is_b { 10_000 } else { 20 },
+            !is_b,
+        )?)),
+        14 => debugger::interact(Debugger::new(day14::SandSimulation::new(input, is_b)?)),
+        day => return Err(format!("day {} has no step-through simulation", day).into()),
+    }?;
+
+    Ok(())
+}